@@ -8,10 +8,10 @@
 
 use bevy::{
     a11y::AccessibilityNode,
-    ecs::{component::HookContext, system::SystemId, world::DeferredWorld},
+    ecs::{component::HookContext, world::DeferredWorld},
     input_focus::{
         tab_navigation::{TabGroup, TabIndex, TabNavigationPlugin},
-        InputDispatchPlugin, InputFocus, InputFocusVisible,
+        InputDispatchPlugin,
     },
     prelude::*,
     ui,
@@ -19,9 +19,9 @@ use bevy::{
     winit::{cursor::CursorIcon, WinitSettings},
 };
 use bevy_core_widgets::{
-    hover::Hovering, ButtonClicked, ButtonPressed, Checked, CoreButton, CoreCheckbox, CoreRadio,
-    CoreRadioGroup, CoreSlider, CoreWidgetsPlugin, InteractionDisabled, SliderDragState,
-    ValueChange,
+    ButtonClicked, Callback, Checked, CoreButton, CoreCheckbox, CoreRadio, CoreRadioGroup,
+    CoreSlider, CoreWidgetsPlugin, FocusRingPlugin, FocusRingSettings, InteractionState,
+    Uncontrolled, ValueChange, WidgetEvent,
 };
 
 fn main() {
@@ -31,14 +31,18 @@ fn main() {
             CoreWidgetsPlugin,
             InputDispatchPlugin,
             TabNavigationPlugin,
+            FocusRingPlugin,
         ))
+        .insert_resource(FocusRingSettings {
+            color: colors::FOCUS.into(),
+            ..default()
+        })
         .insert_resource(WinitSettings::desktop_app())
         .add_systems(Startup, setup_view_root)
         .add_systems(
             Update,
             (
                 update_button_bg_colors,
-                update_focus_rect,
                 update_checkbox_colors,
                 update_radio_colors,
                 update_slider_thumb,
@@ -52,8 +56,8 @@ fn setup_view_root(mut commands: Commands) {
     let camera = commands.spawn((Camera::default(), Camera2d)).id();
 
     // Demonstration click handler.
-    let on_click = commands.register_system(|| {
-        info!("Button on_click handler called!");
+    let on_click = commands.register_system(|event: In<WidgetEvent<()>>| {
+        info!("Button {} on_click handler called!", event.source);
     });
 
     commands.spawn((
@@ -74,7 +78,7 @@ fn setup_view_root(mut commands: Commands) {
         TabGroup::default(),
         Children::spawn((
             Spawn(Text::new("Button")),
-            Spawn(buttons_demo(on_click)),
+            Spawn(buttons_demo(Callback::System(on_click))),
             Spawn(Text::new("Checkbox")),
             Spawn(checkbox_demo()),
             Spawn(Text::new("Radio")),
@@ -98,56 +102,39 @@ fn setup_view_root(mut commands: Commands) {
         },
     );
 
-    // Observer for checkboxes that don't have an on_change handler.
+    // Observer for checkboxes that don't have an on_change handler. The checkboxes are
+    // uncontrolled, so they update their own `Checked` state.
     commands.add_observer(
-        |mut trigger: Trigger<ValueChange<bool>>,
-         q_checkbox: Query<&CoreCheckbox>,
-         mut commands: Commands| {
+        |mut trigger: Trigger<ValueChange<bool>>, q_checkbox: Query<&CoreCheckbox>| {
             trigger.propagate(false);
             if q_checkbox.contains(trigger.target()) {
-                // Update checkbox state from event.
-                let is_checked = trigger.event().0;
-                commands
-                    .entity(trigger.target())
-                    .insert(Checked(is_checked));
-                info!("New checkbox state: {:?}", is_checked);
+                info!("New checkbox state: {:?}", trigger.event().0);
             }
         },
     );
 
-    // Observer for radio buttons.
+    // Observer for radio buttons. The radio group is uncontrolled, so it checks the selected
+    // radio button itself.
     commands.add_observer(
         |mut trigger: Trigger<ValueChange<Entity>>,
-         q_radio_group: Query<&Children, With<CoreRadioGroup>>,
-         q_radio: Query<(&ChildOf, &RadioValue), With<CoreRadio>>,
-         mut commands: Commands| {
+         q_radio_group: Query<(), With<CoreRadioGroup>>,
+         q_radio: Query<&RadioValue, With<CoreRadio>>| {
             trigger.propagate(false);
             if q_radio_group.contains(trigger.target()) {
-                // Update checkbox state from event.
-                let selected_entity = trigger.event().0;
-                let (child_of, radio_value) = q_radio.get(selected_entity).unwrap();
-                // Mutual exclusion logic
-                let group_children = q_radio_group.get(child_of.parent()).unwrap();
-                for radio_child in group_children.iter() {
-                    if let Ok((_, value)) = q_radio.get(radio_child) {
-                        commands
-                            .entity(radio_child)
-                            .insert(Checked(value.0 == radio_value.0));
-                    }
+                if let Ok(radio_value) = q_radio.get(trigger.event().0) {
+                    info!("Radio Value: {}", radio_value.0);
                 }
-                info!("Radio Value: {}", radio_value.0);
             }
         },
     );
 
-    // Observer for sliders that don't have an on_change handler.
+    // Observer for sliders that don't have an on_change handler. The sliders are uncontrolled,
+    // so they store the new value themselves.
     commands.add_observer(
-        |mut trigger: Trigger<ValueChange<f32>>, mut q_slider: Query<&mut CoreSlider>| {
+        |mut trigger: Trigger<ValueChange<f32>>, q_slider: Query<&CoreSlider>| {
             trigger.propagate(false);
-            if let Ok(mut slider) = q_slider.get_mut(trigger.target()) {
-                // Update slider state from event.
-                slider.set_value(trigger.event().0);
-                info!("New slider state: {:?}", slider.value());
+            if q_slider.contains(trigger.target()) {
+                info!("New slider state: {:?}", trigger.event().0);
             }
         },
     );
@@ -176,34 +163,8 @@ pub enum ButtonVariant {
     Selected,
 }
 
-// Places an outline around the currently focused widget. This is a generic implementation for demo
-// purposes; in a real widget library the focus rectangle would be customized to the shape of
-// the individual widgets.
-#[allow(clippy::type_complexity)]
-fn update_focus_rect(
-    mut query: Query<(Entity, Has<Outline>), With<TabIndex>>,
-    focus: Res<InputFocus>,
-    focus_visible: ResMut<InputFocusVisible>,
-    mut commands: Commands,
-) {
-    for (control, has_focus) in query.iter_mut() {
-        let needs_focus = Some(control) == focus.0 && focus_visible.0;
-        if needs_focus != has_focus {
-            if needs_focus {
-                commands.entity(control).insert(Outline {
-                    color: colors::FOCUS.into(),
-                    width: ui::Val::Px(2.0),
-                    offset: ui::Val::Px(1.0),
-                });
-            } else {
-                commands.entity(control).remove::<Outline>();
-            }
-        }
-    }
-}
-
 /// Create a row of demo buttons
-fn buttons_demo(on_click: SystemId) -> impl Bundle {
+fn buttons_demo(on_click: Callback) -> impl Bundle {
     (
         Node {
             display: ui::Display::Flex,
@@ -216,9 +177,9 @@ fn buttons_demo(on_click: SystemId) -> impl Bundle {
             ..default()
         },
         Children::spawn((
-            Spawn(button("Open...", ButtonVariant::Default, Some(on_click))),
-            Spawn(button("Save", ButtonVariant::Default, None)),
-            Spawn(button("Create", ButtonVariant::Primary, None)),
+            Spawn(button("Open...", ButtonVariant::Default, on_click)),
+            Spawn(button("Save", ButtonVariant::Default, Callback::Event)),
+            Spawn(button("Create", ButtonVariant::Primary, Callback::Event)),
         )),
     )
 }
@@ -229,7 +190,7 @@ struct DemoButton {
 }
 
 /// Create a demo button
-fn button(caption: &str, variant: ButtonVariant, on_click: Option<SystemId>) -> impl Bundle {
+fn button(caption: &str, variant: ButtonVariant, on_click: Callback) -> impl Bundle {
     (
         Node {
             display: ui::Display::Flex,
@@ -244,7 +205,6 @@ fn button(caption: &str, variant: ButtonVariant, on_click: Option<SystemId>) ->
         },
         BorderRadius::all(ui::Val::Px(4.0)),
         Name::new("Button"),
-        Hovering::default(),
         CursorIcon::System(SystemCursorIcon::Pointer),
         DemoButton { variant },
         CoreButton { on_click },
@@ -264,19 +224,11 @@ fn button(caption: &str, variant: ButtonVariant, on_click: Option<SystemId>) ->
 #[allow(clippy::type_complexity)]
 fn update_button_bg_colors(
     mut query: Query<
-        (
-            &DemoButton,
-            &mut BackgroundColor,
-            &Hovering,
-            &ButtonPressed,
-            Has<InteractionDisabled>,
-        ),
-        Or<(Added<DemoButton>, Changed<Hovering>, Changed<ButtonPressed>)>,
+        (&DemoButton, &mut BackgroundColor, &InteractionState),
+        Or<(Added<DemoButton>, Changed<InteractionState>)>,
     >,
 ) {
-    for (button, mut bg_color, Hovering(is_hovering), ButtonPressed(is_pressed), is_disabled) in
-        query.iter_mut()
-    {
+    for (button, mut bg_color, state) in query.iter_mut() {
         // Update the background color based on the button's state
         let base_color = match button.variant {
             ButtonVariant::Default => colors::U3,
@@ -285,7 +237,7 @@ fn update_button_bg_colors(
             ButtonVariant::Selected => colors::U4,
         };
 
-        let new_color = match (is_disabled, is_pressed, is_hovering) {
+        let new_color = match (state.disabled, state.pressed, state.hovered) {
             (true, _, _) => base_color.with_alpha(0.2),
             (_, true, true) => base_color.lighter(0.07),
             (_, false, true) => base_color.lighter(0.03),
@@ -309,8 +261,8 @@ fn checkbox_demo() -> impl Bundle {
             ..default()
         },
         Children::spawn((
-            Spawn(checkbox("Show Tutorial", true, None)),
-            Spawn(checkbox("Just Kidding", false, None)),
+            Spawn(checkbox("Show Tutorial", true, Callback::Event)),
+            Spawn(checkbox("Just Kidding", false, Callback::Event)),
         )),
     )
 }
@@ -319,7 +271,7 @@ fn checkbox_demo() -> impl Bundle {
 struct DemoCheckbox;
 
 /// Create a demo checkbox
-fn checkbox(caption: &str, checked: bool, on_change: Option<SystemId<In<bool>>>) -> impl Bundle {
+fn checkbox(caption: &str, checked: bool, on_change: Callback<bool>) -> impl Bundle {
     (
         Node {
             display: ui::Display::Flex,
@@ -332,10 +284,10 @@ fn checkbox(caption: &str, checked: bool, on_change: Option<SystemId<In<bool>>>)
         },
         Name::new("Checkbox"),
         AccessibleName(caption.to_string()),
-        Hovering::default(),
         CursorIcon::System(SystemCursorIcon::Pointer),
         DemoCheckbox,
         CoreCheckbox { on_change },
+        Uncontrolled,
         Checked(checked),
         TabIndex(0),
         Children::spawn((
@@ -381,20 +333,20 @@ fn checkbox(caption: &str, checked: bool, on_change: Option<SystemId<In<bool>>>)
 #[allow(clippy::type_complexity)]
 fn update_checkbox_colors(
     mut q_checkbox: Query<
-        (&Checked, &Hovering, Has<InteractionDisabled>, &Children),
+        (&InteractionState, &Children),
         (
             With<DemoCheckbox>,
-            Or<(Added<DemoCheckbox>, Changed<Hovering>, Changed<Checked>)>,
+            Or<(Added<DemoCheckbox>, Changed<InteractionState>)>,
         ),
     >,
     mut q_border_color: Query<(&mut BorderColor, &mut Children), Without<DemoCheckbox>>,
     mut q_bg_color: Query<&mut BackgroundColor, (Without<DemoCheckbox>, Without<Children>)>,
 ) {
-    for (Checked(checked), Hovering(is_hovering), is_disabled, children) in q_checkbox.iter_mut() {
-        let color: Color = if is_disabled {
+    for (state, children) in q_checkbox.iter_mut() {
+        let color: Color = if state.disabled {
             // If the checkbox is disabled, use a lighter color
             colors::U4.with_alpha(0.2)
-        } else if *is_hovering {
+        } else if state.hovered {
             // If hovering, use a lighter color
             colors::U5
         } else {
@@ -426,7 +378,7 @@ fn update_checkbox_colors(
             continue;
         };
 
-        let mark_color: Color = match (is_disabled, *checked) {
+        let mark_color: Color = match (state.disabled, state.checked) {
             (true, true) => colors::PRIMARY.with_alpha(0.5),
             (false, true) => colors::PRIMARY,
             (_, false) => Srgba::NONE,
@@ -453,7 +405,8 @@ fn radio_demo() -> impl Bundle {
             ..default()
         },
         TabIndex(0),
-        CoreRadioGroup { on_change: None },
+        CoreRadioGroup::default(),
+        Uncontrolled,
         Children::spawn((
             Spawn(radio("WKRP", true)),
             Spawn(radio("WPIG", false)),
@@ -484,7 +437,6 @@ fn radio(caption: &str, checked: bool) -> impl Bundle {
         },
         Name::new("Radio"),
         AccessibleName(caption.to_string()),
-        Hovering::default(),
         CursorIcon::System(SystemCursorIcon::Pointer),
         DemoRadio,
         CoreRadio,
@@ -534,20 +486,20 @@ fn radio(caption: &str, checked: bool) -> impl Bundle {
 #[allow(clippy::type_complexity)]
 fn update_radio_colors(
     mut q_radio: Query<
-        (&Checked, &Hovering, Has<InteractionDisabled>, &Children),
+        (&InteractionState, &Children),
         (
             With<DemoRadio>,
-            Or<(Added<DemoRadio>, Changed<Hovering>, Changed<Checked>)>,
+            Or<(Added<DemoRadio>, Changed<InteractionState>)>,
         ),
     >,
     mut q_border_color: Query<(&mut BorderColor, &mut Children), Without<DemoRadio>>,
     mut q_bg_color: Query<&mut BackgroundColor, (Without<DemoRadio>, Without<Children>)>,
 ) {
-    for (Checked(checked), Hovering(is_hovering), is_disabled, children) in q_radio.iter_mut() {
-        let color: Color = if is_disabled {
+    for (state, children) in q_radio.iter_mut() {
+        let color: Color = if state.disabled {
             // If the radio is disabled, use a lighter color
             colors::U4.with_alpha(0.2)
-        } else if *is_hovering {
+        } else if state.hovered {
             // If hovering, use a lighter color
             colors::U5
         } else {
@@ -579,7 +531,7 @@ fn update_radio_colors(
             continue;
         };
 
-        let mark_color: Color = match (is_disabled, *checked) {
+        let mark_color: Color = match (state.disabled, state.checked) {
             (true, true) => colors::PRIMARY.with_alpha(0.5),
             (false, true) => colors::PRIMARY,
             (_, false) => Srgba::NONE,
@@ -607,8 +559,8 @@ fn slider_demo() -> impl Bundle {
             ..default()
         },
         Children::spawn((
-            Spawn(slider("Volume", 0.0, 100.0, 0.0, None)),
-            Spawn(slider("Difficulty", 0.0, 10.0, 5.0, None)),
+            Spawn(slider("Volume", 0.0, 100.0, 0.0, Callback::Event)),
+            Spawn(slider("Difficulty", 0.0, 10.0, 5.0, Callback::Event)),
         )),
     )
 }
@@ -617,13 +569,7 @@ fn slider_demo() -> impl Bundle {
 struct DemoSlider;
 
 /// Create a demo slider
-fn slider(
-    label: &str,
-    min: f32,
-    max: f32,
-    value: f32,
-    on_change: Option<SystemId<In<f32>>>,
-) -> impl Bundle {
+fn slider(label: &str, min: f32, max: f32, value: f32, on_change: Callback<f32>) -> impl Bundle {
     (
         Node {
             display: ui::Display::Flex,
@@ -638,7 +584,6 @@ fn slider(
         },
         Name::new("Slider"),
         AccessibleName(label.to_string()),
-        Hovering::default(),
         CursorIcon::System(SystemCursorIcon::Pointer),
         DemoSlider,
         CoreSlider {
@@ -649,6 +594,7 @@ fn slider(
             thumb_size: 12.0,
             ..default()
         },
+        Uncontrolled,
         TabIndex(0),
         Children::spawn((
             // Slider background rail
@@ -695,28 +641,24 @@ fn slider(
 #[allow(clippy::type_complexity)]
 fn update_slider_thumb(
     mut q_radio: Query<
-        (
-            &CoreSlider,
-            &SliderDragState,
-            &Hovering,
-            Has<InteractionDisabled>,
-            &Children,
-        ),
+        (&CoreSlider, &InteractionState, &Children),
         (
             With<DemoSlider>,
-            Or<(Added<DemoSlider>, Changed<Hovering>, Changed<CoreSlider>)>,
+            Or<(
+                Added<DemoSlider>,
+                Changed<InteractionState>,
+                Changed<CoreSlider>,
+            )>,
         ),
     >,
     mut q_track: Query<&mut Children, Without<DemoSlider>>,
     mut q_thumb: Query<(&mut BackgroundColor, &mut Node), (Without<DemoSlider>, Without<Children>)>,
 ) {
-    for (slider_state, drag_state, Hovering(is_hovering), is_disabled, children) in
-        q_radio.iter_mut()
-    {
-        let color: Color = if is_disabled {
+    for (slider_state, state, children) in q_radio.iter_mut() {
+        let color: Color = if state.disabled {
             // If the slider is disabled, use a lighter color
             colors::U4.with_alpha(0.2)
-        } else if *is_hovering || drag_state.dragging {
+        } else if state.hovered || state.pressed {
             // If hovering, use a lighter color
             colors::U5
         } else {
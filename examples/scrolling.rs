@@ -13,15 +13,15 @@ use bevy::{
         world::DeferredWorld,
     },
     input_focus::{
-        tab_navigation::{TabGroup, TabIndex, TabNavigationPlugin},
-        InputDispatchPlugin, InputFocus, InputFocusVisible,
+        tab_navigation::{TabGroup, TabNavigationPlugin},
+        InputDispatchPlugin,
     },
     prelude::*,
     ui,
 };
 use bevy_core_widgets::{
-    hover::Hovering, CoreScrollbar, CoreScrollbarThumb, CoreSlider, CoreWidgetsPlugin, Orientation,
-    ValueChange,
+    hover::Hovering, CoreScrollbar, CoreScrollbarThumb, CoreSlider, CoreWidgetsPlugin,
+    FocusRingPlugin, FocusRingSettings, Orientation, ValueChange,
 };
 
 fn main() {
@@ -31,12 +31,14 @@ fn main() {
             CoreWidgetsPlugin,
             InputDispatchPlugin,
             TabNavigationPlugin,
+            FocusRingPlugin,
         ))
+        .insert_resource(FocusRingSettings {
+            color: colors::FOCUS.into(),
+            ..default()
+        })
         .add_systems(Startup, setup_view_root)
-        .add_systems(
-            Update,
-            (update_focus_rect, update_scrollbar_thumb, close_on_esc),
-        )
+        .add_systems(Update, (update_scrollbar_thumb, close_on_esc))
         .run();
 }
 
@@ -98,32 +100,6 @@ pub enum ButtonVariant {
     Selected,
 }
 
-// Places an outline around the currently focused widget. This is a generic implementation for demo
-// purposes; in a real widget library the focus rectangle would be customized to the shape of
-// the individual widgets.
-#[allow(clippy::type_complexity)]
-fn update_focus_rect(
-    mut query: Query<(Entity, Has<Outline>), With<TabIndex>>,
-    focus: Res<InputFocus>,
-    focus_visible: ResMut<InputFocusVisible>,
-    mut commands: Commands,
-) {
-    for (control, has_focus) in query.iter_mut() {
-        let needs_focus = Some(control) == focus.0 && focus_visible.0;
-        if needs_focus != has_focus {
-            if needs_focus {
-                commands.entity(control).insert(Outline {
-                    color: colors::FOCUS.into(),
-                    width: ui::Val::Px(2.0),
-                    offset: ui::Val::Px(1.0),
-                });
-            } else {
-                commands.entity(control).remove::<Outline>();
-            }
-        }
-    }
-}
-
 /// Create a scrolling area.
 ///
 /// The "scroll area" is a container that can be scrolled. It has a nested structure which is
@@ -195,7 +171,7 @@ fn scroll_area_demo() -> impl Bundle {
                     grid_column: GridPlacement::start(2),
                     ..default()
                 },
-                Hovering(false),
+                Hovering::default(),
                 CoreScrollbar {
                     orientation: Orientation::Vertical,
                     target: scroll_area_id,
@@ -206,7 +182,7 @@ fn scroll_area_demo() -> impl Bundle {
                         position_type: ui::PositionType::Absolute,
                         ..default()
                     },
-                    Hovering(false),
+                    Hovering::default(),
                     BackgroundColor(colors::U4.into()),
                     BorderRadius::all(ui::Val::Px(4.0)),
                     CoreScrollbarThumb,
@@ -221,7 +197,7 @@ fn scroll_area_demo() -> impl Bundle {
                     grid_column: GridPlacement::start(1),
                     ..default()
                 },
-                Hovering(false),
+                Hovering::default(),
                 CoreScrollbar {
                     orientation: Orientation::Horizontal,
                     target: scroll_area_id,
@@ -232,7 +208,7 @@ fn scroll_area_demo() -> impl Bundle {
                         position_type: ui::PositionType::Absolute,
                         ..default()
                     },
-                    Hovering(false),
+                    Hovering::default(),
                     BackgroundColor(colors::U4.into()),
                     BorderRadius::all(ui::Val::Px(4.0)),
                     CoreScrollbarThumb,
@@ -258,8 +234,8 @@ fn text_row(caption: &str) -> impl Bundle {
 fn update_scrollbar_thumb(
     mut q_thumb: Query<(&CoreScrollbarThumb, &mut BackgroundColor, &Hovering), Changed<Hovering>>,
 ) {
-    for (_thumb, mut thumb_bg, Hovering(is_hovering)) in q_thumb.iter_mut() {
-        let color: Color = if *is_hovering {
+    for (_thumb, mut thumb_bg, hovering) in q_thumb.iter_mut() {
+        let color: Color = if hovering.hovered {
             // If hovering, use a lighter color
             colors::U5
         } else {
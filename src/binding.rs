@@ -0,0 +1,481 @@
+use bevy::{
+    ecs::entity::{EntityMapper, MapEntities},
+    prelude::*,
+    reflect::{
+        DynamicEnum, GetPath, ReflectKind, ReflectRef, TypeData, TypeRegistration, TypeRegistry,
+    },
+    ui::UiSystem,
+};
+
+use crate::{
+    core_slider::clamp_value, core_spin_box::update_spin_box_text,
+    core_text_input::update_text_input_display, Checked, CoreCheckbox, CoreRadio, CoreRadioGroup,
+    CoreSlider, CoreSpinBox, CoreTextArea, CoreTextInput, EditHistory, TextBuffer, ValueChange,
+};
+
+/// Where the value bound by a [`WidgetBinding`] is stored. Types are identified by their full
+/// type path, such as `bevy_transform::components::transform::Transform`, so that bindings can
+/// be saved in scenes.
+#[derive(Debug, Clone, PartialEq, Eq, Reflect)]
+pub enum BindingTarget {
+    /// A component of the given type on an entity.
+    Component(Entity, String),
+    /// A resource of the given type.
+    Resource(String),
+}
+
+impl BindingTarget {
+    /// Look up the registration of the bound type.
+    fn registration<'r>(&self, registry: &'r TypeRegistry) -> Option<&'r TypeRegistration> {
+        let type_path = match self {
+            BindingTarget::Component(_, type_path) | BindingTarget::Resource(type_path) => {
+                type_path
+            }
+        };
+        registry.get_with_type_path(type_path)
+    }
+
+    /// Look up type data for the bound type.
+    fn type_data<'r, T: TypeData>(&self, registry: &'r TypeRegistry) -> Option<&'r T> {
+        self.registration(registry)?.data::<T>()
+    }
+
+    /// Returns true if the bound component or resource has been added or changed since the
+    /// current system last ran. Returns false if it doesn't exist.
+    fn is_changed(&self, world: &World, registry: &TypeRegistry) -> bool {
+        let Some(type_id) = self.registration(registry).map(TypeRegistration::type_id) else {
+            return false;
+        };
+        let ticks = match self {
+            BindingTarget::Component(entity, _) => world
+                .components()
+                .get_id(type_id)
+                .zip(world.get_entity(*entity).ok())
+                .and_then(|(id, entity)| entity.get_change_ticks_by_id(id)),
+            BindingTarget::Resource(_) => world
+                .components()
+                .get_resource_id(type_id)
+                .and_then(|id| world.get_resource_change_ticks_by_id(id)),
+        };
+        ticks.is_some_and(|ticks| {
+            ticks.is_changed(world.last_change_tick(), world.read_change_tick())
+        })
+    }
+}
+
+impl MapEntities for BindingTarget {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        if let BindingTarget::Component(entity, _) = self {
+            *entity = entity_mapper.get_mapped(*entity);
+        }
+    }
+}
+
+/// Binds the value of a widget to a field of a component or resource, using reflection. The
+/// component or resource type must be registered in the [`AppTypeRegistry`], along with its
+/// [`ReflectComponent`] or [`ReflectResource`] data.
+///
+/// Whenever the field changes, the new value is pushed into the widget: the `value` of a
+/// [`CoreSlider`] or [`CoreSpinBox`], the [`Checked`] state of a [`CoreCheckbox`], or the
+/// [`TextBuffer`] of a [`CoreTextInput`] or [`CoreTextArea`]. The text is not replaced while the
+/// user is in the middle of editing it. A [`CoreRadioGroup`] checks the radio button whose
+/// [`BindingOption`] matches the value. In the other direction, [`ValueChange`] events emitted by
+/// the widget are written back to the field, so the widget's change callback must be left as
+/// [`Callback::Event`]. Slider values are clamped to the slider's range before they are written.
+///
+/// Values are converted between types where needed: a slider can be bound to any numeric field,
+/// a checkbox to a numeric field (which is checked if non-zero), and a text input to a number,
+/// a `bool` or a `String`. An enum field is bound by the name of its variant, and can be set to
+/// any of its unit variants. Values which can't be converted, such as text which isn't a valid
+/// number, are ignored.
+///
+/// ```ignore
+/// commands.spawn((
+///     CoreSlider::default(),
+///     WidgetBinding::component::<Transform>(player, "translation.x"),
+/// ));
+/// ```
+///
+/// [`Callback::Event`]: crate::Callback::Event
+#[derive(Component, Debug, Clone, Reflect)]
+#[reflect(Component, Debug)]
+pub struct WidgetBinding {
+    #[entities]
+    pub target: BindingTarget,
+    /// The reflection path of the field within the component or resource, such as
+    /// `translation.x`. An empty path binds the whole value.
+    pub path: String,
+}
+
+impl WidgetBinding {
+    /// Bind to a field of the component `C` on `entity`.
+    pub fn component<C: Component + TypePath>(entity: Entity, path: impl Into<String>) -> Self {
+        Self {
+            target: BindingTarget::Component(entity, C::type_path().to_string()),
+            path: path.into(),
+        }
+    }
+
+    /// Bind to a field of the resource `R`.
+    pub fn resource<R: Resource + TypePath>(path: impl Into<String>) -> Self {
+        Self {
+            target: BindingTarget::Resource(R::type_path().to_string()),
+            path: path.into(),
+        }
+    }
+
+    /// Read the bound field from the world.
+    fn read(&self, world: &World, registry: &TypeRegistry) -> Option<BoundValue> {
+        let source = match self.target {
+            BindingTarget::Component(entity, _) => self
+                .target
+                .type_data::<ReflectComponent>(registry)?
+                .reflect(world.get_entity(entity).ok()?)?,
+            BindingTarget::Resource(_) => self
+                .target
+                .type_data::<ReflectResource>(registry)?
+                .reflect(world)
+                .ok()?,
+        };
+        BoundValue::read(source.reflect_path(self.path.as_str()).ok()?)
+    }
+
+    /// Write a value to the bound field, converting it to the type of the field.
+    fn write(&self, world: &mut World, value: &BoundValue) {
+        let Some(registry) = world.get_resource::<AppTypeRegistry>().cloned() else {
+            return;
+        };
+        let registry = registry.read();
+        let source = match self.target {
+            BindingTarget::Component(entity, _) => {
+                let Some(reflect_component) = self.target.type_data::<ReflectComponent>(&registry)
+                else {
+                    return;
+                };
+                let Ok(entity) = world.get_entity_mut(entity) else {
+                    return;
+                };
+                reflect_component.reflect_mut(entity)
+            }
+            BindingTarget::Resource(_) => self
+                .target
+                .type_data::<ReflectResource>(&registry)
+                .and_then(|reflect_resource| reflect_resource.reflect_mut(world).ok()),
+        };
+        let Some(mut source) = source else {
+            return;
+        };
+        // Only trigger change detection if the value actually changes.
+        let Ok(field) = source
+            .bypass_change_detection()
+            .reflect_path_mut(self.path.as_str())
+        else {
+            return;
+        };
+        if BoundValue::read(field).as_ref() == Some(value) {
+            return;
+        }
+        if let Ok(field) = source.reflect_path_mut(self.path.as_str()) {
+            value.write(field);
+        }
+    }
+}
+
+/// The value that a [`CoreRadio`] stands for, when its [`CoreRadioGroup`] has a
+/// [`WidgetBinding`]. Selecting the radio button writes this value to the bound field, converting
+/// it as for a text input: numeric fields are parsed from it, and enum fields are set to the unit
+/// variant with this name.
+#[derive(Component, Debug, Clone, PartialEq, Eq, Reflect)]
+#[reflect(Component, Debug, PartialEq)]
+pub struct BindingOption(pub String);
+
+/// A bound value, in the form used to convert it between types.
+#[derive(Debug, Clone, PartialEq)]
+enum BoundValue {
+    Bool(bool),
+    Number(f64),
+    Text(String),
+}
+
+/// Read or write a reflected field as each of the listed numeric types.
+macro_rules! numeric_field {
+    (read $field:ident, $($ty:ty),*) => {
+        $(
+            if let Some(value) = $field.try_downcast_ref::<$ty>() {
+                return Some(BoundValue::Number(*value as f64));
+            }
+        )*
+    };
+    (write $field:ident, $value:ident, $($ty:ty),*) => {
+        $(
+            if let Some(field) = $field.try_downcast_mut::<$ty>() {
+                if let Some(value) = $value.as_number() {
+                    // Float-to-int casts saturate, so out-of-range values are clamped.
+                    *field = value.round() as $ty;
+                }
+                return;
+            }
+        )*
+    };
+}
+
+impl BoundValue {
+    fn read(field: &dyn PartialReflect) -> Option<Self> {
+        if let Some(value) = field.try_downcast_ref::<bool>() {
+            return Some(Self::Bool(*value));
+        }
+        if let Some(value) = field.try_downcast_ref::<f32>() {
+            return Some(Self::Number(*value as f64));
+        }
+        if let Some(value) = field.try_downcast_ref::<f64>() {
+            return Some(Self::Number(*value));
+        }
+        numeric_field!(read field, i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+        if let Some(value) = field.try_downcast_ref::<String>() {
+            return Some(Self::Text(value.clone()));
+        }
+        if let ReflectRef::Enum(value) = field.reflect_ref() {
+            return Some(Self::Text(value.variant_name().to_string()));
+        }
+        None
+    }
+
+    fn write(&self, field: &mut dyn PartialReflect) {
+        if let Some(field) = field.try_downcast_mut::<bool>() {
+            if let Some(value) = self.as_bool() {
+                *field = value;
+            }
+            return;
+        }
+        if let Some(field) = field.try_downcast_mut::<f32>() {
+            if let Some(value) = self.as_number() {
+                *field = value as f32;
+            }
+            return;
+        }
+        if let Some(field) = field.try_downcast_mut::<f64>() {
+            if let Some(value) = self.as_number() {
+                *field = value;
+            }
+            return;
+        }
+        if let Some(field) = field.try_downcast_mut::<String>() {
+            *field = self.as_text();
+            return;
+        }
+        numeric_field!(write field, self, i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+        if field.reflect_kind() == ReflectKind::Enum {
+            // Fails, leaving the field unchanged, if there is no unit variant with this name.
+            let _ = field.try_apply(&DynamicEnum::new(self.as_text(), ()));
+        }
+    }
+
+    fn as_bool(&self) -> Option<bool> {
+        match self {
+            Self::Bool(value) => Some(*value),
+            Self::Number(value) => Some(*value != 0.),
+            Self::Text(text) => text.trim().parse().ok(),
+        }
+    }
+
+    fn as_number(&self) -> Option<f64> {
+        match self {
+            Self::Bool(value) => Some(if *value { 1. } else { 0. }),
+            Self::Number(value) => Some(*value),
+            Self::Text(text) => text
+                .trim()
+                .parse()
+                .ok()
+                .filter(|value: &f64| value.is_finite()),
+        }
+    }
+
+    fn as_text(&self) -> String {
+        match self {
+            Self::Bool(value) => value.to_string(),
+            Self::Number(value) => value.to_string(),
+            Self::Text(text) => text.clone(),
+        }
+    }
+}
+
+/// Queue a write of a widget's new value to its bound field.
+fn write_binding(commands: &mut Commands, binding: &WidgetBinding, value: BoundValue) {
+    let binding = binding.clone();
+    commands.queue(move |world: &mut World| binding.write(world, &value));
+}
+
+#[allow(clippy::type_complexity)]
+fn binding_on_number_change(
+    trigger: Trigger<ValueChange<f32>>,
+    q_binding: Query<
+        (&WidgetBinding, Option<&CoreSlider>),
+        Or<(With<CoreSlider>, With<CoreSpinBox>)>,
+    >,
+    mut commands: Commands,
+) {
+    if let Ok((binding, slider)) = q_binding.get(trigger.target()) {
+        let mut value = trigger.event().0;
+        if let Some(slider) = slider {
+            value = clamp_value(value, slider.min, slider.max);
+        }
+        write_binding(&mut commands, binding, BoundValue::Number(value as f64));
+    }
+}
+
+fn binding_on_bool_change(
+    trigger: Trigger<ValueChange<bool>>,
+    q_binding: Query<&WidgetBinding, With<CoreCheckbox>>,
+    mut commands: Commands,
+) {
+    if let Ok(binding) = q_binding.get(trigger.target()) {
+        write_binding(&mut commands, binding, BoundValue::Bool(trigger.event().0));
+    }
+}
+
+#[allow(clippy::type_complexity)]
+fn binding_on_text_change(
+    trigger: Trigger<ValueChange<String>>,
+    q_binding: Query<
+        &WidgetBinding,
+        (
+            Or<(With<CoreTextInput>, With<CoreTextArea>)>,
+            // Spin boxes are bound by their numeric value.
+            Without<CoreSpinBox>,
+        ),
+    >,
+    mut commands: Commands,
+) {
+    if let Ok(binding) = q_binding.get(trigger.target()) {
+        write_binding(
+            &mut commands,
+            binding,
+            BoundValue::Text(trigger.event().0.clone()),
+        );
+    }
+}
+
+fn binding_on_selection_change(
+    trigger: Trigger<ValueChange<Entity>>,
+    q_binding: Query<&WidgetBinding, With<CoreRadioGroup>>,
+    q_option: Query<&BindingOption, With<CoreRadio>>,
+    mut commands: Commands,
+) {
+    if let Ok(binding) = q_binding.get(trigger.target()) {
+        if let Ok(option) = q_option.get(trigger.event().0) {
+            write_binding(&mut commands, binding, BoundValue::Text(option.0.clone()));
+        }
+    }
+}
+
+/// Check the radio buttons of a bound radio group whose option matches `value`.
+fn update_bound_radio_group(world: &mut World, group: Entity, value: &BoundValue) {
+    let Some(children) = world.get::<Children>(group) else {
+        return;
+    };
+    let text = value.as_text();
+    let radios = children
+        .iter()
+        .filter_map(|radio| {
+            let option = world.get::<BindingOption>(radio)?;
+            let checked = world.get::<Checked>(radio)?;
+            let selected = option.0 == text;
+            (checked.0 != selected).then_some((radio, selected))
+        })
+        .collect::<Vec<_>>();
+    for (radio, selected) in radios {
+        world.entity_mut(radio).insert(Checked(selected));
+    }
+}
+
+/// System which pushes the values of bound fields into their widgets. A binding is only read when
+/// its source has changed since the last run, or when the binding or the widget's value has
+/// changed, such as after an edit which wasn't written back because it couldn't be converted.
+#[allow(clippy::type_complexity)]
+fn update_bound_widgets(
+    world: &mut World,
+    q_binding: &mut QueryState<(
+        Entity,
+        Ref<WidgetBinding>,
+        Option<Ref<CoreSlider>>,
+        Option<Ref<CoreSpinBox>>,
+        Option<Ref<Checked>>,
+        Option<Ref<TextBuffer>>,
+    )>,
+) {
+    let Some(registry) = world.get_resource::<AppTypeRegistry>().cloned() else {
+        return;
+    };
+    let registry = registry.read();
+    let values = q_binding
+        .iter(world)
+        .filter(|(_, binding, slider, spin_box, checked, buffer)| {
+            binding.is_changed()
+                || slider.as_ref().is_some_and(Ref::is_changed)
+                || spin_box.as_ref().is_some_and(Ref::is_changed)
+                || checked.as_ref().is_some_and(Ref::is_changed)
+                || buffer.as_ref().is_some_and(Ref::is_changed)
+                || binding.target.is_changed(world, &registry)
+        })
+        .filter_map(|(widget, binding, ..)| Some((widget, binding.read(world, &registry)?)))
+        .collect::<Vec<_>>();
+    for (widget, value) in values {
+        let Ok(mut entity) = world.get_entity_mut(widget) else {
+            continue;
+        };
+        if entity.contains::<CoreRadioGroup>() {
+            update_bound_radio_group(world, widget, &value);
+        } else if let Some(mut slider) = entity.get_mut::<CoreSlider>() {
+            if let Some(value) = value.as_number().map(|value| value as f32) {
+                if slider.value != clamp_value(value, slider.min, slider.max) {
+                    slider.set_value(value);
+                }
+            }
+        } else if let Some(mut spin_box) = entity.get_mut::<CoreSpinBox>() {
+            if let Some(value) = value.as_number().map(|value| value as f32) {
+                if spin_box.value != clamp_value(value, spin_box.min, spin_box.max) {
+                    spin_box.set_value(value);
+                }
+            }
+        } else if entity.contains::<CoreCheckbox>() {
+            if let Some(value) = value.as_bool() {
+                if entity
+                    .get::<Checked>()
+                    .is_none_or(|checked| checked.0 != value)
+                {
+                    entity.insert(Checked(value));
+                }
+            }
+        } else if let Some(mut buffer) = entity.get_mut::<TextBuffer>() {
+            let text = value.as_text();
+            if !buffer.uncommitted && buffer.text() != text {
+                buffer.set_text(text);
+                // The old text can't be restored by undo once the app has replaced it.
+                if let Some(mut history) = entity.get_mut::<EditHistory>() {
+                    history.clear();
+                }
+            }
+        }
+    }
+}
+
+/// Plugin which keeps [`WidgetBinding`]s in sync with their bound fields.
+pub struct WidgetBindingPlugin;
+
+impl Plugin for WidgetBindingPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_observer(binding_on_number_change)
+            .add_observer(binding_on_bool_change)
+            .add_observer(binding_on_text_change)
+            .add_observer(binding_on_selection_change)
+            .register_type::<WidgetBinding>()
+            .register_type::<BindingOption>()
+            .add_systems(
+                PostUpdate,
+                update_bound_widgets
+                    .before(update_spin_box_text)
+                    .before(update_text_input_display)
+                    .before(UiSystem::Content),
+            );
+    }
+}
@@ -0,0 +1,265 @@
+use alloc::sync::Arc;
+use core::{
+    any::{type_name, Any},
+    fmt,
+};
+
+use bevy::{
+    ecs::{component::HookContext, system::SystemId, world::DeferredWorld},
+    platform::collections::HashMap,
+    prelude::*,
+};
+
+use crate::{
+    CoreBarrier, CoreButton, CoreCheckbox, CoreDialog, CoreRadioGroup, CoreScrubField, CoreSlider,
+    CoreSpinBox, CoreTextInput,
+};
+
+/// The input passed to a [`Callback::System`]: the widget which triggered the callback, and the
+/// value it reports. Knowing the source widget means that a single system can be shared between
+/// many widgets.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct WidgetEvent<T> {
+    /// The widget entity.
+    pub source: Entity,
+    /// The value reported by the widget, such as the new checked state of a checkbox.
+    pub value: T,
+}
+
+/// A closure which can be used as a [`Callback`].
+pub type CallbackFn<T> = dyn Fn(&mut World, Entity, T) + Send + Sync;
+
+/// How a widget notifies the app when something happens, such as a button being clicked or a
+/// value being changed. `T` is the value the widget reports, if any.
+///
+/// By default, widgets trigger an event (such as [`ValueChange`] or [`ButtonClicked`]) on the
+/// widget entity, which propagates up the hierarchy so that a single observer can handle many
+/// widgets.
+///
+/// Callbacks are not reflected, since systems and closures can't be serialized. Widgets which
+/// are loaded from a scene have the default callback, [`Callback::Event`], unless they have a
+/// [`NamedCallbacks`] component.
+///
+/// [`ValueChange`]: crate::ValueChange
+/// [`ButtonClicked`]: crate::ButtonClicked
+#[derive(Default)]
+pub enum Callback<T: 'static = ()> {
+    /// Run a registered system, passing the widget entity and the value as a [`WidgetEvent`].
+    System(SystemId<In<WidgetEvent<T>>>),
+    /// Run a closure with exclusive access to the world. The closure is passed the widget entity
+    /// and the value.
+    Closure(Arc<CallbackFn<T>>),
+    /// Run the callback registered under this name in the [`CallbackRegistry`]. The name is
+    /// looked up each time the callback is called, so the registered callback can be replaced
+    /// while the app is running. The registered callback can't itself be `Named`, since that
+    /// could form a cycle.
+    Named(String),
+    /// Trigger the widget's event on the widget entity.
+    #[default]
+    Event,
+    /// Do nothing.
+    Ignore,
+}
+
+impl<T: 'static> Callback<T> {
+    /// Create a callback which runs a closure. See [`Callback::Closure`].
+    pub fn closure(func: impl Fn(&mut World, Entity, T) + Send + Sync + 'static) -> Self {
+        Self::Closure(Arc::new(func))
+    }
+}
+
+impl<T: Send + 'static> Callback<T> {
+    /// Notify the app that `source` has produced `value`. `event` constructs the event to
+    /// trigger if this is [`Callback::Event`].
+    pub(crate) fn notify<E: Event>(
+        &self,
+        commands: &mut Commands,
+        source: Entity,
+        value: T,
+        event: impl FnOnce(T) -> E + Send + 'static,
+    ) {
+        match self {
+            Callback::System(system) => {
+                commands.run_system_with(*system, WidgetEvent { source, value });
+            }
+            Callback::Closure(func) => {
+                let func = func.clone();
+                commands.queue(move |world: &mut World| func(world, source, value));
+            }
+            Callback::Named(name) => {
+                let name = name.clone();
+                commands.queue(move |world: &mut World| {
+                    let callback = world
+                        .get_resource::<CallbackRegistry>()
+                        .and_then(|registry| registry.get::<T>(&name))
+                        .cloned();
+                    match callback {
+                        Some(Callback::Named(target)) => warn!(
+                            "Callback named `{name}` refers to another named callback, `{target}`"
+                        ),
+                        Some(callback) => {
+                            callback.notify(&mut world.commands(), source, value, event);
+                            world.flush();
+                        }
+                        None => warn!(
+                            "No callback named `{name}` taking `{}` is registered",
+                            type_name::<T>()
+                        ),
+                    }
+                });
+            }
+            Callback::Event => {
+                commands.trigger_targets(event(value), source);
+            }
+            Callback::Ignore => {}
+        }
+    }
+}
+
+impl<T: 'static> Clone for Callback<T> {
+    fn clone(&self) -> Self {
+        match self {
+            Self::System(system) => Self::System(*system),
+            Self::Closure(func) => Self::Closure(func.clone()),
+            Self::Named(name) => Self::Named(name.clone()),
+            Self::Event => Self::Event,
+            Self::Ignore => Self::Ignore,
+        }
+    }
+}
+
+impl<T: 'static> fmt::Debug for Callback<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::System(system) => f.debug_tuple("System").field(system).finish(),
+            Self::Closure(_) => f.write_str("Closure"),
+            Self::Named(name) => f.debug_tuple("Named").field(name).finish(),
+            Self::Event => f.write_str("Event"),
+            Self::Ignore => f.write_str("Ignore"),
+        }
+    }
+}
+
+impl<T: 'static> From<SystemId<In<WidgetEvent<T>>>> for Callback<T> {
+    fn from(system: SystemId<In<WidgetEvent<T>>>) -> Self {
+        Self::System(system)
+    }
+}
+
+/// Resource which maps names to callbacks, so that widgets can refer to callbacks by name. This
+/// is how widgets which are loaded from a file, such as a
+/// [`WidgetTemplate`](crate::WidgetTemplate), are connected to the app.
+///
+/// Each name refers to a callback with a particular value type: a callback registered for a
+/// button, which reports `()`, can't be used by a slider, which reports `f32`.
+#[derive(Resource, Default)]
+pub struct CallbackRegistry {
+    callbacks: HashMap<String, Box<dyn Any + Send + Sync>>,
+}
+
+impl CallbackRegistry {
+    /// Register a callback under `name`, replacing any callback which was previously registered
+    /// with that name. The callback must not be [`Callback::Named`].
+    pub fn register<T: 'static>(
+        &mut self,
+        name: impl Into<String>,
+        callback: impl Into<Callback<T>>,
+    ) {
+        self.callbacks
+            .insert(name.into(), Box::new(callback.into()));
+    }
+
+    /// Get the callback registered under `name`, if there is one for values of type `T`.
+    pub fn get<T: 'static>(&self, name: &str) -> Option<&Callback<T>> {
+        self.callbacks.get(name)?.downcast_ref()
+    }
+
+    /// Remove the callback registered under `name`.
+    pub fn unregister(&mut self, name: &str) {
+        self.callbacks.remove(name);
+    }
+}
+
+/// Component which sets the callbacks of a widget to [`Callback::Named`], mapping the callback
+/// field (`on_click`, `on_change`, `on_commit` or `on_close`) to the name of a callback in the
+/// [`CallbackRegistry`]. Unlike the callbacks themselves, this component can be reflected, so it
+/// can be used in scenes and widget templates.
+///
+/// The callbacks are set when this component is inserted, so it should be inserted along with,
+/// or after, the widget component. When it is removed, the callbacks are reset to
+/// [`Callback::Event`].
+#[derive(Component, Debug, Clone, Default, Reflect)]
+#[reflect(Component, Debug, Default)]
+#[component(on_insert = on_insert_named_callbacks, on_replace = on_replace_named_callbacks)]
+pub struct NamedCallbacks(pub HashMap<String, String>);
+
+fn on_insert_named_callbacks(world: DeferredWorld, context: HookContext) {
+    set_named_callbacks(world, context.entity, true);
+}
+
+fn on_replace_named_callbacks(world: DeferredWorld, context: HookContext) {
+    set_named_callbacks(world, context.entity, false);
+}
+
+/// Set each callback listed in the entity's [`NamedCallbacks`] to [`Callback::Named`], or reset
+/// it to the widget's default if `named` is false.
+fn set_named_callbacks(mut world: DeferredWorld, entity: Entity, named: bool) {
+    let Some(callbacks) = world.get::<NamedCallbacks>(entity).cloned() else {
+        return;
+    };
+    let mut entity_mut = world.entity_mut(entity);
+
+    // Set the callback on the first of the listed widgets which the entity has.
+    macro_rules! set_callback {
+        ($name:expr, $($widget:ty => $field:ident),+) => {
+            'set: {
+                $(
+                    if let Some(mut widget) = entity_mut.get_mut::<$widget>() {
+                        widget.$field = if named {
+                            Callback::Named($name)
+                        } else {
+                            <$widget>::default().$field
+                        };
+                        break 'set true;
+                    }
+                )+
+                false
+            }
+        };
+    }
+
+    for (field, name) in callbacks.0 {
+        let applied = match field.as_str() {
+            "on_click" => set_callback!(name, CoreButton => on_click),
+            // Spin boxes are also text inputs, and scrub fields are also spin boxes, so the more
+            // specific widgets come first.
+            "on_change" => set_callback!(
+                name,
+                CoreSpinBox => on_change,
+                CoreSlider => on_change,
+                CoreCheckbox => on_change,
+                CoreRadioGroup => on_change,
+                CoreTextInput => on_change
+            ),
+            "on_commit" => {
+                // The text input of a spin box always commits to the spin box.
+                if entity_mut.contains::<CoreSpinBox>() && !entity_mut.contains::<CoreScrubField>()
+                {
+                    false
+                } else {
+                    set_callback!(
+                        name,
+                        CoreScrubField => on_commit,
+                        CoreSlider => on_commit,
+                        CoreTextInput => on_commit
+                    )
+                }
+            }
+            "on_close" => set_callback!(name, CoreDialog => on_close, CoreBarrier => on_close),
+            _ => false,
+        };
+        if named && !applied {
+            warn!("Entity {entity} has no widget with a `{field}` callback");
+        }
+    }
+}
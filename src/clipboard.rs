@@ -0,0 +1,129 @@
+use bevy::prelude::*;
+
+/// A source and destination for copied text. Implement this to connect the [`Clipboard`]
+/// resource to a custom clipboard, for example one shared with a remote session.
+pub trait ClipboardBackend: Send + Sync + 'static {
+    /// Get the text currently on the clipboard, if any.
+    fn get_text(&mut self) -> Option<String>;
+
+    /// Place `text` on the clipboard.
+    fn set_text(&mut self, text: &str);
+}
+
+/// A clipboard which only exists within the current process. This is the default backend, and
+/// is useful for tests and for running without a display server.
+#[derive(Debug, Default, Clone)]
+pub struct MemoryClipboard(Option<String>);
+
+impl ClipboardBackend for MemoryClipboard {
+    fn get_text(&mut self) -> Option<String> {
+        self.0.clone()
+    }
+
+    fn set_text(&mut self, text: &str) {
+        self.0 = Some(text.to_string());
+    }
+}
+
+/// A clipboard backend which uses the operating system clipboard. If the system clipboard can't
+/// be accessed, it falls back to an in-process clipboard.
+///
+/// The connection to the system clipboard is opened on first use and kept for the lifetime of
+/// the backend, since on some platforms (such as Linux) copied text is lost when the last
+/// connection is closed.
+#[cfg(feature = "system_clipboard")]
+#[derive(Default)]
+pub struct SystemClipboard {
+    clipboard: Option<arboard::Clipboard>,
+    fallback: MemoryClipboard,
+}
+
+#[cfg(feature = "system_clipboard")]
+impl SystemClipboard {
+    /// Get the connection to the system clipboard, opening it if needed.
+    fn clipboard(&mut self) -> Option<&mut arboard::Clipboard> {
+        if self.clipboard.is_none() {
+            self.clipboard = arboard::Clipboard::new().ok();
+        }
+        self.clipboard.as_mut()
+    }
+}
+
+#[cfg(feature = "system_clipboard")]
+impl Clone for SystemClipboard {
+    /// The clone opens its own connection to the system clipboard when it is first used.
+    fn clone(&self) -> Self {
+        Self {
+            clipboard: None,
+            fallback: self.fallback.clone(),
+        }
+    }
+}
+
+#[cfg(feature = "system_clipboard")]
+impl core::fmt::Debug for SystemClipboard {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("SystemClipboard")
+            .field("connected", &self.clipboard.is_some())
+            .field("fallback", &self.fallback)
+            .finish()
+    }
+}
+
+#[cfg(feature = "system_clipboard")]
+impl ClipboardBackend for SystemClipboard {
+    fn get_text(&mut self) -> Option<String> {
+        match self.clipboard() {
+            Some(clipboard) => clipboard.get_text().ok(),
+            None => self.fallback.get_text(),
+        }
+    }
+
+    fn set_text(&mut self, text: &str) {
+        match self.clipboard() {
+            Some(clipboard) => {
+                if let Err(err) = clipboard.set_text(text) {
+                    warn!("Failed to set clipboard text: {err}");
+                }
+            }
+            None => self.fallback.set_text(text),
+        }
+    }
+}
+
+/// Resource used by text widgets to copy, cut and paste text. Apps can also use it to implement
+/// their own copy and paste commands.
+///
+/// By default this uses an in-process [`MemoryClipboard`]; with the `system_clipboard` feature
+/// enabled, it uses the operating system clipboard instead. A different backend can be used by
+/// inserting a `Clipboard` resource before adding the [`CoreTextInputPlugin`].
+///
+/// [`CoreTextInputPlugin`]: crate::CoreTextInputPlugin
+#[derive(Resource)]
+pub struct Clipboard(Box<dyn ClipboardBackend>);
+
+impl Clipboard {
+    /// Create a clipboard which uses the given backend.
+    pub fn new(backend: impl ClipboardBackend) -> Self {
+        Self(Box::new(backend))
+    }
+
+    /// Get the text currently on the clipboard, if any.
+    pub fn get_text(&mut self) -> Option<String> {
+        self.0.get_text()
+    }
+
+    /// Place `text` on the clipboard.
+    pub fn set_text(&mut self, text: &str) {
+        self.0.set_text(text);
+    }
+}
+
+impl Default for Clipboard {
+    fn default() -> Self {
+        #[cfg(feature = "system_clipboard")]
+        return Self::new(SystemClipboard::default());
+        #[cfg(not(feature = "system_clipboard"))]
+        return Self::new(MemoryClipboard::default());
+    }
+}
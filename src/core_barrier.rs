@@ -1,17 +1,31 @@
 use bevy::{
-    ecs::system::SystemId,
     input::{keyboard::KeyboardInput, ButtonState},
     input_focus::{FocusedInput, InputFocus, InputFocusVisible},
     prelude::*,
 };
 
+use crate::{events::DialogCloseRequested, Callback};
+
 /// A "barrier" is a backdrop element, one that covers the entire screen, blocks click events
 /// from reaching elements behind it, and can be used to close a dialog or menu.
 ///
-/// The `on_close` field is a system that will be run when the barrier gets a mouse down event.
-#[derive(Component, Debug)]
+/// The `on_close` callback will be called when the barrier gets a mouse down event, or when the
+/// `Escape` key is pressed while the barrier is focused. By default it does nothing, so that a
+/// barrier only blocks clicks; set it to [`Callback::Event`] to emit a [`DialogCloseRequested`]
+/// event.
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component, Debug, Default)]
 pub struct CoreBarrier {
-    pub on_close: Option<SystemId>,
+    #[reflect(ignore)]
+    pub on_close: Callback,
+}
+
+impl Default for CoreBarrier {
+    fn default() -> Self {
+        Self {
+            on_close: Callback::Ignore,
+        }
+    }
 }
 
 pub(crate) fn barrier_on_key_input(
@@ -24,11 +38,14 @@ pub(crate) fn barrier_on_key_input(
         if event.state == ButtonState::Pressed
             && !event.repeat
             && (event.key_code == KeyCode::Escape)
+            && !matches!(bstate.on_close, Callback::Ignore)
         {
-            if let Some(on_close) = bstate.on_close {
-                trigger.propagate(false);
-                commands.run_system(on_close);
-            }
+            trigger.propagate(false);
+            bstate
+                .on_close
+                .notify(&mut commands, trigger.target(), (), |_| {
+                    DialogCloseRequested
+                });
         }
     }
 }
@@ -45,9 +62,9 @@ pub(crate) fn barrier_on_pointer_down(
         focus.0 = Some(entity_id);
         focus_visible.0 = false;
         trigger.propagate(false);
-        if let Some(on_close) = bstate.on_close {
-            commands.run_system(on_close);
-        }
+        bstate
+            .on_close
+            .notify(&mut commands, entity_id, (), |_| DialogCloseRequested);
     }
 }
 
@@ -56,6 +73,7 @@ pub struct CoreBarrierPlugin;
 impl Plugin for CoreBarrierPlugin {
     fn build(&self, app: &mut App) {
         app.add_observer(barrier_on_key_input)
-            .add_observer(barrier_on_pointer_down);
+            .add_observer(barrier_on_pointer_down)
+            .register_type::<CoreBarrier>();
     }
 }
@@ -1,27 +1,31 @@
 use accesskit::Role;
 use bevy::{
     a11y::AccessibilityNode,
-    ecs::system::SystemId,
     input::keyboard::KeyboardInput,
     input_focus::{FocusedInput, InputFocus, InputFocusVisible},
     prelude::*,
 };
 
-use crate::{events::ButtonClicked, ButtonPressed, InteractionDisabled};
+use crate::{
+    events::ButtonClicked, interaction_states::InteractionStatesPlugin, ButtonPressed, Callback,
+    EffectivelyDisabled, InteractionState,
+};
 
-/// Headless button widget. The `on_click` field is a system that will be run when the button
-/// is clicked, or when the Enter or Space key is pressed while the button is focused. If the
-/// `on_click` field is `None`, the button will emit a `ButtonClicked` event when clicked.
-#[derive(Component, Debug)]
+/// Headless button widget. The `on_click` callback will be called when the button is clicked, or
+/// when the Enter or Space key is pressed while the button is focused. By default, the button
+/// will emit a `ButtonClicked` event when clicked.
+#[derive(Component, Debug, Default, Reflect)]
+#[reflect(Component, Debug, Default)]
 #[require(AccessibilityNode(accesskit::Node::new(Role::Button)))]
-#[require(ButtonPressed)]
+#[require(ButtonPressed, InteractionState)]
 pub struct CoreButton {
-    pub on_click: Option<SystemId>,
+    #[reflect(ignore)]
+    pub on_click: Callback,
 }
 
 pub(crate) fn button_on_key_event(
     mut trigger: Trigger<FocusedInput<KeyboardInput>>,
-    q_state: Query<(&CoreButton, Has<InteractionDisabled>)>,
+    q_state: Query<(&CoreButton, Has<EffectivelyDisabled>)>,
     mut commands: Commands,
 ) {
     if let Ok((bstate, disabled)) = q_state.get(trigger.target()) {
@@ -30,12 +34,12 @@ pub(crate) fn button_on_key_event(
             if !event.repeat
                 && (event.key_code == KeyCode::Enter || event.key_code == KeyCode::Space)
             {
-                if let Some(on_click) = bstate.on_click {
+                if !matches!(bstate.on_click, Callback::Event) {
                     trigger.propagate(false);
-                    commands.run_system(on_click);
-                } else {
-                    commands.trigger_targets(ButtonClicked, trigger.target());
                 }
+                bstate
+                    .on_click
+                    .notify(&mut commands, trigger.target(), (), |_| ButtonClicked);
             }
         }
     }
@@ -43,31 +47,29 @@ pub(crate) fn button_on_key_event(
 
 pub(crate) fn button_on_pointer_click(
     mut trigger: Trigger<Pointer<Click>>,
-    mut q_state: Query<(&CoreButton, &mut ButtonPressed, Has<InteractionDisabled>)>,
+    mut q_state: Query<(&CoreButton, &mut ButtonPressed, Has<EffectivelyDisabled>)>,
     mut commands: Commands,
 ) {
     if let Ok((bstate, pressed, disabled)) = q_state.get_mut(trigger.target()) {
         trigger.propagate(false);
-        if pressed.0 && !disabled {
-            if let Some(on_click) = bstate.on_click {
-                commands.run_system(on_click);
-            } else {
-                commands.trigger_targets(ButtonClicked, trigger.target());
-            }
+        if pressed.is_pressed_by(trigger.pointer_id) && !disabled {
+            bstate
+                .on_click
+                .notify(&mut commands, trigger.target(), (), |_| ButtonClicked);
         }
     }
 }
 
 pub(crate) fn button_on_pointer_down(
     mut trigger: Trigger<Pointer<Pressed>>,
-    mut q_state: Query<(&mut ButtonPressed, Has<InteractionDisabled>)>,
+    mut q_state: Query<(&mut ButtonPressed, Has<EffectivelyDisabled>), With<CoreButton>>,
     mut focus: ResMut<InputFocus>,
     mut focus_visible: ResMut<InputFocusVisible>,
 ) {
     if let Ok((mut pressed, disabled)) = q_state.get_mut(trigger.target()) {
         trigger.propagate(false);
         if !disabled {
-            pressed.0 = true;
+            pressed.press(trigger.pointer_id);
             focus.0 = Some(trigger.target());
             focus_visible.0 = false;
         }
@@ -76,36 +78,36 @@ pub(crate) fn button_on_pointer_down(
 
 pub(crate) fn button_on_pointer_up(
     mut trigger: Trigger<Pointer<Released>>,
-    mut q_state: Query<(&mut ButtonPressed, Has<InteractionDisabled>)>,
+    mut q_state: Query<(&mut ButtonPressed, Has<EffectivelyDisabled>), With<CoreButton>>,
 ) {
     if let Ok((mut pressed, disabled)) = q_state.get_mut(trigger.target()) {
         trigger.propagate(false);
         if !disabled {
-            pressed.0 = false;
+            pressed.release(trigger.pointer_id);
         }
     }
 }
 
 pub(crate) fn button_on_pointer_drag_end(
     mut trigger: Trigger<Pointer<DragEnd>>,
-    mut q_state: Query<(&mut ButtonPressed, Has<InteractionDisabled>)>,
+    mut q_state: Query<(&mut ButtonPressed, Has<EffectivelyDisabled>), With<CoreButton>>,
 ) {
     if let Ok((mut pressed, disabled)) = q_state.get_mut(trigger.target()) {
         trigger.propagate(false);
         if !disabled {
-            pressed.0 = false;
+            pressed.release(trigger.pointer_id);
         }
     }
 }
 
 pub(crate) fn button_on_pointer_cancel(
     mut trigger: Trigger<Pointer<Cancel>>,
-    mut q_state: Query<(&mut ButtonPressed, Has<InteractionDisabled>)>,
+    mut q_state: Query<(&mut ButtonPressed, Has<EffectivelyDisabled>), With<CoreButton>>,
 ) {
     if let Ok((mut pressed, disabled)) = q_state.get_mut(trigger.target()) {
         trigger.propagate(false);
         if !disabled {
-            pressed.0 = false;
+            pressed.release(trigger.pointer_id);
         }
     }
 }
@@ -114,11 +116,13 @@ pub struct CoreButtonPlugin;
 
 impl Plugin for CoreButtonPlugin {
     fn build(&self, app: &mut App) {
+        InteractionStatesPlugin::add_once(app);
         app.add_observer(button_on_key_event)
             .add_observer(button_on_pointer_down)
             .add_observer(button_on_pointer_up)
             .add_observer(button_on_pointer_click)
             .add_observer(button_on_pointer_drag_end)
-            .add_observer(button_on_pointer_cancel);
+            .add_observer(button_on_pointer_cancel)
+            .register_type::<CoreButton>();
     }
 }
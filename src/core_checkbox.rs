@@ -1,66 +1,133 @@
 use accesskit::Role;
 use bevy::{
     a11y::AccessibilityNode,
-    ecs::system::SystemId,
     input::{keyboard::KeyboardInput, ButtonState},
     input_focus::{FocusedInput, InputFocus, InputFocusVisible},
     prelude::*,
 };
 
-use crate::{interaction_states::Checked, InteractionDisabled, ValueChange};
+use crate::{
+    edit_history::{report_widget_edit, WidgetEdit, WidgetEditHook, WidgetValue},
+    interaction_states::{Checked, InteractionStatesPlugin},
+    Callback, EffectivelyDisabled, InteractionReadOnly, InteractionState, Uncontrolled,
+    ValueChange,
+};
 
-/// Headless widget implementation for checkboxes. The `checked` represents the current state
-/// of the checkbox. The `on_change` field is a system that will be run when the checkbox
-/// is clicked, or when the Enter or Space key is pressed while the checkbox is focused.
-/// If the `on_change` field is `None`, the checkbox will emit a `ValueChange` event instead.
-#[derive(Component, Debug)]
+/// Headless widget implementation for checkboxes. The [`Checked`] component represents the
+/// current state of the checkbox. The `on_change` callback will be called with the new state when
+/// the checkbox is clicked, or when the Enter or Space key is pressed while the checkbox is
+/// focused. By default, the checkbox will emit a `ValueChange` event. The checkbox doesn't update
+/// its own `Checked` state unless it is [`Uncontrolled`].
+#[derive(Component, Debug, Default, Reflect)]
+#[reflect(Component, Debug, Default)]
 #[require(AccessibilityNode(accesskit::Node::new(Role::CheckBox)), Checked)]
+#[require(InteractionState)]
 pub struct CoreCheckbox {
-    pub on_change: Option<SystemId<In<bool>>>,
+    #[reflect(ignore)]
+    pub on_change: Callback<bool>,
+}
+
+/// Report the new state of the checkbox, and apply it if the checkbox is uncontrolled.
+fn toggle_checkbox(
+    commands: &mut Commands,
+    entity: Entity,
+    checkbox: &CoreCheckbox,
+    is_checked: bool,
+    uncontrolled: bool,
+) {
+    if uncontrolled {
+        commands.entity(entity).insert(Checked(!is_checked));
+    }
+    checkbox
+        .on_change
+        .notify(commands, entity, !is_checked, ValueChange);
 }
 
+#[allow(clippy::type_complexity)]
 fn checkbox_on_key_input(
     mut trigger: Trigger<FocusedInput<KeyboardInput>>,
-    q_state: Query<(&CoreCheckbox, &Checked, Has<InteractionDisabled>)>,
+    q_state: Query<(
+        &CoreCheckbox,
+        &Checked,
+        Has<EffectivelyDisabled>,
+        Has<InteractionReadOnly>,
+        Has<Uncontrolled>,
+    )>,
+    edit_hook: Option<Res<WidgetEditHook>>,
     mut commands: Commands,
 ) {
-    if let Ok((checkbox, checked, disabled)) = q_state.get(trigger.target()) {
+    if let Ok((checkbox, checked, disabled, read_only, uncontrolled)) =
+        q_state.get(trigger.target())
+    {
         let event = &trigger.event().input;
         if !disabled
+            && !read_only
             && event.state == ButtonState::Pressed
             && !event.repeat
             && (event.key_code == KeyCode::Enter || event.key_code == KeyCode::Space)
         {
             let is_checked = checked.0;
             trigger.propagate(false);
-            if let Some(on_change) = checkbox.on_change {
-                commands.run_system_with(on_change, !is_checked);
-            } else {
-                commands.trigger_targets(ValueChange(!is_checked), trigger.target());
-            }
+            toggle_checkbox(
+                &mut commands,
+                trigger.target(),
+                checkbox,
+                is_checked,
+                uncontrolled,
+            );
+            report_widget_edit(
+                &mut commands,
+                edit_hook.as_deref(),
+                WidgetEdit {
+                    entity: trigger.target(),
+                    before: WidgetValue::Bool(is_checked),
+                    after: WidgetValue::Bool(!is_checked),
+                },
+            );
         }
     }
 }
 
+#[allow(clippy::type_complexity)]
 fn checkbox_on_pointer_click(
     mut trigger: Trigger<Pointer<Click>>,
-    q_state: Query<(&CoreCheckbox, &Checked, Has<InteractionDisabled>)>,
+    q_state: Query<(
+        &CoreCheckbox,
+        &Checked,
+        Has<EffectivelyDisabled>,
+        Has<InteractionReadOnly>,
+        Has<Uncontrolled>,
+    )>,
     mut focus: ResMut<InputFocus>,
     mut focus_visible: ResMut<InputFocusVisible>,
+    edit_hook: Option<Res<WidgetEditHook>>,
     mut commands: Commands,
 ) {
-    if let Ok((checkbox, checked, disabled)) = q_state.get(trigger.target()) {
+    if let Ok((checkbox, checked, disabled, read_only, uncontrolled)) =
+        q_state.get(trigger.target())
+    {
         let checkbox_id = trigger.target();
         focus.0 = Some(checkbox_id);
         focus_visible.0 = false;
         trigger.propagate(false);
-        if !disabled {
+        if !disabled && !read_only {
             let is_checked = checked.0;
-            if let Some(on_change) = checkbox.on_change {
-                commands.run_system_with(on_change, !is_checked);
-            } else {
-                commands.trigger_targets(ValueChange(!is_checked), trigger.target());
-            }
+            toggle_checkbox(
+                &mut commands,
+                checkbox_id,
+                checkbox,
+                is_checked,
+                uncontrolled,
+            );
+            report_widget_edit(
+                &mut commands,
+                edit_hook.as_deref(),
+                WidgetEdit {
+                    entity: trigger.target(),
+                    before: WidgetValue::Bool(is_checked),
+                    after: WidgetValue::Bool(!is_checked),
+                },
+            );
         }
     }
 }
@@ -69,7 +136,9 @@ pub struct CoreCheckboxPlugin;
 
 impl Plugin for CoreCheckboxPlugin {
     fn build(&self, app: &mut App) {
+        InteractionStatesPlugin::add_once(app);
         app.add_observer(checkbox_on_key_input)
-            .add_observer(checkbox_on_pointer_click);
+            .add_observer(checkbox_on_pointer_click)
+            .register_type::<CoreCheckbox>();
     }
 }
@@ -0,0 +1,237 @@
+use accesskit::Role;
+use bevy::{
+    a11y::AccessibilityNode,
+    ecs::{component::HookContext, world::DeferredWorld},
+    input::{keyboard::KeyboardInput, ButtonState},
+    input_focus::{
+        tab_navigation::{TabGroup, TabIndex},
+        FocusedInput, InputFocus, InputFocusVisible,
+    },
+    prelude::*,
+};
+
+use crate::{events::DialogCloseRequested, Callback};
+
+/// Headless widget implementation for modal dialogs. While a dialog is open, keyboard focus is
+/// kept inside of it: focus is moved into the dialog when it opens, and `Tab` / `Shift-Tab`
+/// cycle only through the focusable (that is, having a [`TabIndex`]) descendants of the dialog.
+/// The dialog is a modal [`TabGroup`], so that this also works when focus is on one of its
+/// descendants and the `TabNavigationPlugin` is handling `Tab`. When the dialog is closed (either by despawning it or removing this component), focus is
+/// restored to whatever entity had focus before the dialog was opened.
+///
+/// Dialogs can be nested: each open dialog is pushed on to the [`DialogStack`], and only the
+/// topmost dialog traps focus and responds to the `Escape` key.
+///
+/// The `on_close` callback will be called when the user presses `Escape` while the dialog is the
+/// topmost one. By default, the dialog will emit a [`DialogCloseRequested`] event. In either
+/// case, it is the app's responsibility to actually close the dialog.
+#[derive(Component, Debug, Default, Reflect)]
+#[reflect(Component, Debug, Default)]
+#[require(AccessibilityNode(accesskit::Node::new(Role::Dialog)))]
+#[require(TabGroup = TabGroup::modal())]
+#[component(on_add = on_add_dialog, on_remove = on_remove_dialog)]
+pub struct CoreDialog {
+    #[reflect(ignore)]
+    pub on_close: Callback,
+    /// If true, the dialog is presented to assistive technologies as an alert dialog, that is,
+    /// a dialog which conveys an urgent message and requires a response.
+    pub alert: bool,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct DialogStackEntry {
+    dialog: Entity,
+    prev_focus: Option<Entity>,
+}
+
+/// Resource which keeps track of the currently open modal dialogs, in the order in which they
+/// were opened.
+#[derive(Resource, Debug, Default)]
+pub struct DialogStack(Vec<DialogStackEntry>);
+
+impl DialogStack {
+    /// The topmost (most recently opened) dialog, if any.
+    pub fn top(&self) -> Option<Entity> {
+        self.0.last().map(|entry| entry.dialog)
+    }
+
+    /// Returns true if there are no open dialogs.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Iterate over the open dialogs, from bottom to top.
+    pub fn iter(&self) -> impl Iterator<Item = Entity> + '_ {
+        self.0.iter().map(|entry| entry.dialog)
+    }
+}
+
+// Hook to register the dialog with the stack and set the a11y role.
+fn on_add_dialog(mut world: DeferredWorld, context: HookContext) {
+    let prev_focus = world.get_resource::<InputFocus>().and_then(|focus| focus.0);
+    if let Some(mut stack) = world.get_resource_mut::<DialogStack>() {
+        stack.0.push(DialogStackEntry {
+            dialog: context.entity,
+            prev_focus,
+        });
+    }
+
+    let mut entt = world.entity_mut(context.entity);
+    let alert = entt.get::<CoreDialog>().unwrap().alert;
+    if let Some(mut accessibility) = entt.get_mut::<AccessibilityNode>() {
+        accessibility.set_role(if alert {
+            Role::AlertDialog
+        } else {
+            Role::Dialog
+        });
+        accessibility.set_modal();
+    }
+}
+
+// Hook to remove the dialog from the stack and restore the previous focus.
+fn on_remove_dialog(mut world: DeferredWorld, context: HookContext) {
+    let Some(mut stack) = world.get_resource_mut::<DialogStack>() else {
+        return;
+    };
+    let Some(index) = stack.0.iter().position(|e| e.dialog == context.entity) else {
+        return;
+    };
+    let entry = stack.0.remove(index);
+    if index < stack.0.len() {
+        // A dialog further down the stack was closed. The dialog above it was opened while
+        // focus was inside the closed dialog, so hand it our saved focus instead.
+        stack.0[index].prev_focus = entry.prev_focus;
+        return;
+    }
+
+    let restore = entry
+        .prev_focus
+        .filter(|prev| world.get_entity(*prev).is_ok());
+    if let Some(mut focus) = world.get_resource_mut::<InputFocus>() {
+        focus.0 = restore;
+    }
+}
+
+/// Collect the focusable descendants of `parent` in tree order.
+fn gather_focusable(
+    out: &mut Vec<(Entity, TabIndex)>,
+    parent: Entity,
+    q_children: &Query<&Children>,
+    q_tab_index: &Query<&TabIndex>,
+) {
+    let Ok(children) = q_children.get(parent) else {
+        return;
+    };
+    for child in children.iter() {
+        if let Ok(tab_index) = q_tab_index.get(child) {
+            if tab_index.0 >= 0 {
+                out.push((child, *tab_index));
+            }
+        }
+        gather_focusable(out, child, q_children, q_tab_index);
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn dialog_on_key_input(
+    mut trigger: Trigger<FocusedInput<KeyboardInput>>,
+    q_dialog: Query<&CoreDialog>,
+    q_children: Query<&Children>,
+    q_tab_index: Query<&TabIndex>,
+    stack: Res<DialogStack>,
+    keys: Res<ButtonInput<KeyCode>>,
+    mut focus: ResMut<InputFocus>,
+    mut focus_visible: ResMut<InputFocusVisible>,
+    mut commands: Commands,
+) {
+    let dialog_id = trigger.target();
+    let Ok(dialog) = q_dialog.get(dialog_id) else {
+        return;
+    };
+    if stack.top() != Some(dialog_id) {
+        return;
+    }
+
+    let event = &trigger.event().input;
+    if event.state != ButtonState::Pressed || event.repeat {
+        return;
+    }
+
+    match event.key_code {
+        KeyCode::Escape => {
+            trigger.propagate(false);
+            dialog
+                .on_close
+                .notify(&mut commands, dialog_id, (), |_| DialogCloseRequested);
+        }
+        KeyCode::Tab => {
+            trigger.propagate(false);
+            let mut focusable: Vec<(Entity, TabIndex)> = Vec::new();
+            gather_focusable(&mut focusable, dialog_id, &q_children, &q_tab_index);
+            if focusable.is_empty() {
+                focus.0 = Some(dialog_id);
+                return;
+            }
+            // Stable sort by tab index, preserving tree order for equal indices.
+            focusable.sort_by_key(|(_, index)| *index);
+            let count = focusable.len();
+            let current = focusable.iter().position(|(e, _)| Some(*e) == focus.0);
+            let backwards = keys.pressed(KeyCode::ShiftLeft) || keys.pressed(KeyCode::ShiftRight);
+            let next = match (current, backwards) {
+                (Some(index), false) => (index + 1) % count,
+                (Some(index), true) => (index + count - 1) % count,
+                (None, false) => 0,
+                (None, true) => count - 1,
+            };
+            focus.0 = Some(focusable[next].0);
+            focus_visible.0 = true;
+        }
+        _ => {}
+    }
+}
+
+/// System which moves focus into the topmost dialog whenever it is somewhere else, for example
+/// when the dialog has just been opened or the user clicked outside of it. Focus is only left on
+/// the dialog itself if it has no focusable descendants, so that `Tab` is always handled from
+/// within the dialog's tab group.
+fn dialog_contain_focus(
+    stack: Res<DialogStack>,
+    mut focus: ResMut<InputFocus>,
+    q_parents: Query<&ChildOf>,
+    q_children: Query<&Children>,
+    q_tab_index: Query<&TabIndex>,
+) {
+    if !stack.is_changed() && !focus.is_changed() {
+        return;
+    }
+    let Some(dialog_id) = stack.top() else {
+        return;
+    };
+    if let Some(focus_id) = focus.0 {
+        if q_parents.iter_ancestors(focus_id).any(|e| e == dialog_id) {
+            return;
+        }
+    }
+
+    let mut focusable: Vec<(Entity, TabIndex)> = Vec::new();
+    gather_focusable(&mut focusable, dialog_id, &q_children, &q_tab_index);
+    focusable.sort_by_key(|(_, index)| *index);
+    let target = focusable
+        .first()
+        .map(|(entity, _)| *entity)
+        .unwrap_or(dialog_id);
+    if focus.0 != Some(target) {
+        focus.0 = Some(target);
+    }
+}
+
+pub struct CoreDialogPlugin;
+
+impl Plugin for CoreDialogPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DialogStack>()
+            .register_type::<CoreDialog>()
+            .add_observer(dialog_on_key_input)
+            .add_systems(PostUpdate, dialog_contain_focus);
+    }
+}
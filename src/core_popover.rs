@@ -0,0 +1,224 @@
+use bevy::{prelude::*, transform::TransformSystem, ui::UiSystem};
+
+/// Which side of the anchor element the popover should be placed on.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Debug, Default, PartialEq)]
+pub enum PopoverSide {
+    Top,
+    #[default]
+    Bottom,
+    Left,
+    Right,
+}
+
+impl PopoverSide {
+    /// The side opposite to this one.
+    pub fn flip(self) -> Self {
+        match self {
+            PopoverSide::Top => PopoverSide::Bottom,
+            PopoverSide::Bottom => PopoverSide::Top,
+            PopoverSide::Left => PopoverSide::Right,
+            PopoverSide::Right => PopoverSide::Left,
+        }
+    }
+}
+
+/// How the popover should be aligned with the anchor element, along the edge it is placed on.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Debug, Default, PartialEq)]
+pub enum PopoverAlign {
+    #[default]
+    Start,
+    Center,
+    End,
+}
+
+/// A headless popover, which positions itself next to an anchor element. This can be used to
+/// build dropdown menus, tooltips, and other floating elements.
+///
+/// After layout, the popover's `left` and `top` are set so that it sits on the requested side of
+/// the anchor. If the popover would overflow the window, it is flipped to the opposite side of the
+/// anchor; if it still doesn't fit, it is shifted along the edge to keep it on screen. The
+/// position is recomputed every frame, so the popover follows the anchor when it moves or is
+/// scrolled.
+///
+/// The popover entity will have its `position_type` set to `Absolute`. Popovers are typically
+/// spawned as root UI nodes, so that they are not clipped by the anchor's ancestors.
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component, Debug)]
+#[require(PopoverPlacement)]
+pub struct CorePopover {
+    /// The entity that the popover is positioned relative to.
+    #[entities]
+    pub anchor: Entity,
+    /// The preferred side of the anchor to place the popover on.
+    pub side: PopoverSide,
+    /// The alignment of the popover along the side of the anchor.
+    pub align: PopoverAlign,
+    /// Gap between the anchor and the popover, in logical pixels.
+    pub offset: f32,
+}
+
+impl CorePopover {
+    pub fn new(anchor: Entity) -> Self {
+        Self {
+            anchor,
+            side: PopoverSide::default(),
+            align: PopoverAlign::default(),
+            offset: 0.0,
+        }
+    }
+}
+
+/// Component which records the side of the anchor that the popover was actually placed on,
+/// after flipping. Styling code can use this to, for example, point an arrow at the anchor.
+#[derive(Component, Debug, Default, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Component, Debug, Default, PartialEq)]
+pub struct PopoverPlacement(pub PopoverSide);
+
+/// Compute the logical-pixel rectangle occupied by a UI node.
+fn node_rect(node: &ComputedNode, transform: &GlobalTransform) -> Rect {
+    let scale = node.inverse_scale_factor;
+    Rect::from_center_size(
+        transform.translation().truncate() * scale,
+        node.size() * scale,
+    )
+}
+
+/// Compute the position of the top-left corner of a popover of the given `size`, and the side
+/// of the anchor it ended up on.
+fn place_popover(
+    popover: &CorePopover,
+    anchor: Rect,
+    size: Vec2,
+    bounds: Vec2,
+) -> (Vec2, PopoverSide) {
+    let position_on = |side: PopoverSide| -> Vec2 {
+        let cross = |start: f32, end: f32, extent: f32| match popover.align {
+            PopoverAlign::Start => start,
+            PopoverAlign::Center => (start + end - extent) * 0.5,
+            PopoverAlign::End => end - extent,
+        };
+        match side {
+            PopoverSide::Top => Vec2::new(
+                cross(anchor.min.x, anchor.max.x, size.x),
+                anchor.min.y - popover.offset - size.y,
+            ),
+            PopoverSide::Bottom => Vec2::new(
+                cross(anchor.min.x, anchor.max.x, size.x),
+                anchor.max.y + popover.offset,
+            ),
+            PopoverSide::Left => Vec2::new(
+                anchor.min.x - popover.offset - size.x,
+                cross(anchor.min.y, anchor.max.y, size.y),
+            ),
+            PopoverSide::Right => Vec2::new(
+                anchor.max.x + popover.offset,
+                cross(anchor.min.y, anchor.max.y, size.y),
+            ),
+        }
+    };
+
+    // Amount by which the popover overflows the window along the main axis of `side`.
+    let overflow = |side: PopoverSide, pos: Vec2| -> f32 {
+        match side {
+            PopoverSide::Top | PopoverSide::Bottom => {
+                (-pos.y).max(0.) + (pos.y + size.y - bounds.y).max(0.)
+            }
+            PopoverSide::Left | PopoverSide::Right => {
+                (-pos.x).max(0.) + (pos.x + size.x - bounds.x).max(0.)
+            }
+        }
+    };
+
+    let mut side = popover.side;
+    let mut pos = position_on(side);
+    let preferred_overflow = overflow(side, pos);
+    if preferred_overflow > 0. {
+        let flipped = side.flip();
+        let flipped_pos = position_on(flipped);
+        if overflow(flipped, flipped_pos) < preferred_overflow {
+            side = flipped;
+            pos = flipped_pos;
+        }
+    }
+
+    // Shift along the edge to keep the popover inside the window.
+    match side {
+        PopoverSide::Top | PopoverSide::Bottom => {
+            pos.x = pos.x.min(bounds.x - size.x).max(0.);
+        }
+        PopoverSide::Left | PopoverSide::Right => {
+            pos.y = pos.y.min(bounds.y - size.y).max(0.);
+        }
+    }
+
+    (pos, side)
+}
+
+fn update_popover_position(
+    mut q_popover: Query<(
+        &CorePopover,
+        &mut Node,
+        &mut PopoverPlacement,
+        &ComputedNode,
+        &ComputedNodeTarget,
+        &GlobalTransform,
+    )>,
+    q_anchor: Query<(&ComputedNode, &GlobalTransform), Without<CorePopover>>,
+) {
+    for (popover, mut node, mut placement, computed, target, transform) in q_popover.iter_mut() {
+        let Ok((anchor_node, anchor_transform)) = q_anchor.get(popover.anchor) else {
+            continue;
+        };
+
+        let anchor_rect = node_rect(anchor_node, anchor_transform);
+        let current_rect = node_rect(computed, transform);
+        let (pos, side) = place_popover(
+            popover,
+            anchor_rect,
+            current_rect.size(),
+            target.logical_size(),
+        );
+
+        // The popover may not be a root node, so rather than working out the origin of its
+        // containing block, adjust its current offsets by the distance it needs to move.
+        let delta = pos - current_rect.min;
+        let left = match node.left {
+            Val::Px(left) => left,
+            _ => 0.,
+        };
+        let top = match node.top {
+            Val::Px(top) => top,
+            _ => 0.,
+        };
+
+        if node.position_type != PositionType::Absolute {
+            node.position_type = PositionType::Absolute;
+        }
+        if delta.x.abs() > 0.5 || !matches!(node.left, Val::Px(_)) {
+            node.left = Val::Px(left + delta.x);
+        }
+        if delta.y.abs() > 0.5 || !matches!(node.top, Val::Px(_)) {
+            node.top = Val::Px(top + delta.y);
+        }
+        if placement.0 != side {
+            placement.0 = side;
+        }
+    }
+}
+
+pub struct CorePopoverPlugin;
+
+impl Plugin for CorePopoverPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<CorePopover>()
+            .register_type::<PopoverPlacement>()
+            .add_systems(
+                PostUpdate,
+                update_popover_position
+                    .after(UiSystem::Layout)
+                    .after(TransformSystem::TransformPropagate),
+            );
+    }
+}
@@ -5,7 +5,10 @@ use bevy::{
     prelude::*,
 };
 
-use crate::{interaction_states::Checked, ButtonClicked, InteractionDisabled};
+use crate::{
+    interaction_states::{Checked, InteractionStatesPlugin},
+    ButtonClicked, EffectivelyDisabled, InteractionState,
+};
 
 /// Headless widget implementation for radio buttons. Note that this does not handle the mutual
 /// exclusion of radio buttons in the same group; that should be handled by the parent component.
@@ -18,13 +21,15 @@ use crate::{interaction_states::Checked, ButtonClicked, InteractionDisabled};
 /// According to the WAI-ARIA best practices document, radio buttons should not be focusable,
 /// but rather the enclosing group should be focusable.
 /// See https://www.w3.org/WAI/ARIA/apg/patterns/radio/
-#[derive(Component, Debug)]
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component, Debug)]
 #[require(AccessibilityNode(accesskit::Node::new(Role::RadioButton)), Checked)]
+#[require(InteractionState)]
 pub struct CoreRadio;
 
 fn radio_on_pointer_click(
     mut trigger: Trigger<Pointer<Click>>,
-    q_state: Query<(&Checked, Has<InteractionDisabled>), With<CoreRadio>>,
+    q_state: Query<(&Checked, Has<EffectivelyDisabled>), With<CoreRadio>>,
     mut focus: ResMut<InputFocus>,
     mut focus_visible: ResMut<InputFocusVisible>,
     mut commands: Commands,
@@ -46,6 +51,8 @@ pub struct CoreRadioPlugin;
 
 impl Plugin for CoreRadioPlugin {
     fn build(&self, app: &mut App) {
-        app.add_observer(radio_on_pointer_click);
+        InteractionStatesPlugin::add_once(app);
+        app.add_observer(radio_on_pointer_click)
+            .register_type::<CoreRadio>();
     }
 }
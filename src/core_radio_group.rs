@@ -1,13 +1,17 @@
 use accesskit::Role;
 use bevy::{
     a11y::AccessibilityNode,
-    ecs::system::SystemId,
     input::{keyboard::KeyboardInput, ButtonState},
     input_focus::{FocusedInput, InputFocus, InputFocusVisible},
     prelude::*,
 };
 
-use crate::{ButtonClicked, Checked, CoreRadio, InteractionDisabled, ValueChange};
+use crate::{
+    edit_history::{report_widget_edit, WidgetEdit, WidgetEditHook, WidgetValue},
+    interaction_states::InteractionStatesPlugin,
+    ButtonClicked, Callback, Checked, CoreRadio, EffectivelyDisabled, InteractionReadOnly,
+    InteractionState, Uncontrolled, ValueChange,
+};
 
 /// Headless widget implementation for a "radio group". This component is used to group multiple
 /// `CoreRadio` components together, allowing them to behave as a single unit. It implements
@@ -18,21 +22,43 @@ use crate::{ButtonClicked, Checked, CoreRadio, InteractionDisabled, ValueChange}
 /// components to trigger a `ButtonPress` event, and tranforms this into a `ValueChange` event
 /// which contains the id of the selected button. The app can then derive the selected value
 /// from this using app-specific data.
-#[derive(Component, Debug)]
+///
+/// If the group is [`Uncontrolled`], it also updates the [`Checked`] state of its radio buttons,
+/// so that only the selected button is checked.
+#[derive(Component, Debug, Default, Reflect)]
+#[reflect(Component, Debug, Default)]
 #[require(AccessibilityNode(accesskit::Node::new(Role::RadioGroup)))]
+#[require(InteractionState)]
 pub struct CoreRadioGroup {
-    pub on_change: Option<SystemId<In<Entity>>>,
+    #[reflect(ignore)]
+    pub on_change: Callback<Entity>,
+}
+
+/// Check the `selected` radio button and uncheck the others, for an uncontrolled radio group.
+fn check_radio(commands: &mut Commands, radios: impl Iterator<Item = Entity>, selected: Entity) {
+    for radio in radios {
+        commands.entity(radio).insert(Checked(radio == selected));
+    }
 }
 
 fn radio_group_on_key_input(
     mut trigger: Trigger<FocusedInput<KeyboardInput>>,
-    q_group: Query<(&CoreRadioGroup, &Children)>,
-    q_radio: Query<(&Checked, Has<InteractionDisabled>), With<CoreRadio>>,
+    q_group: Query<(
+        &CoreRadioGroup,
+        &Children,
+        Has<InteractionReadOnly>,
+        Has<Uncontrolled>,
+    )>,
+    q_radio: Query<(&Checked, Has<EffectivelyDisabled>), With<CoreRadio>>,
+    edit_hook: Option<Res<WidgetEditHook>>,
     mut commands: Commands,
 ) {
-    if let Ok((CoreRadioGroup { on_change }, group_children)) = q_group.get(trigger.target()) {
+    if let Ok((CoreRadioGroup { on_change }, group_children, read_only, uncontrolled)) =
+        q_group.get(trigger.target())
+    {
         let event = &trigger.event().input;
-        if event.state == ButtonState::Pressed
+        if !read_only
+            && event.state == ButtonState::Pressed
             && !event.repeat
             && matches!(
                 event.key_code,
@@ -104,21 +130,43 @@ fn radio_group_on_key_input(
             let (next_id, _) = radio_children[next_index];
 
             // Trigger the on_change event for the newly checked radio button
-            if let Some(on_change) = on_change {
-                commands.run_system_with(*on_change, next_id);
-            } else {
-                commands.trigger_targets(ValueChange(next_id), trigger.target());
+            if uncontrolled {
+                check_radio(
+                    &mut commands,
+                    group_children
+                        .iter()
+                        .filter(|child| q_radio.contains(*child)),
+                    next_id,
+                );
             }
+            on_change.notify(&mut commands, trigger.target(), next_id, ValueChange);
+            report_widget_edit(
+                &mut commands,
+                edit_hook.as_deref(),
+                WidgetEdit {
+                    entity: trigger.target(),
+                    before: WidgetValue::Selection(
+                        radio_children.get(current_index).map(|(id, _)| *id),
+                    ),
+                    after: WidgetValue::Selection(Some(next_id)),
+                },
+            );
         }
     }
 }
 
 fn radio_group_on_button_click(
     mut trigger: Trigger<ButtonClicked>,
-    q_group: Query<(&CoreRadioGroup, &Children)>,
-    q_radio: Query<(&Checked, &ChildOf, Has<InteractionDisabled>), With<CoreRadio>>,
+    q_group: Query<(
+        &CoreRadioGroup,
+        &Children,
+        Has<InteractionReadOnly>,
+        Has<Uncontrolled>,
+    )>,
+    q_radio: Query<(&Checked, &ChildOf, Has<EffectivelyDisabled>), With<CoreRadio>>,
     mut focus: ResMut<InputFocus>,
     mut focus_visible: ResMut<InputFocusVisible>,
+    edit_hook: Option<Res<WidgetEditHook>>,
     mut commands: Commands,
 ) {
     let radio_id = trigger.target();
@@ -130,7 +178,9 @@ fn radio_group_on_button_click(
 
     // Find the parent CoreRadioGroup of the clicked radio button.
     let group_id = child_of.parent();
-    let Ok((CoreRadioGroup { on_change }, group_children)) = q_group.get(group_id) else {
+    let Ok((CoreRadioGroup { on_change }, group_children, read_only, uncontrolled)) =
+        q_group.get(group_id)
+    else {
         // The radio button's parent is not a CoreRadioGroup, ignore the click
         warn!("Radio button clicked without a valid CoreRadioGroup parent");
         return;
@@ -140,6 +190,12 @@ fn radio_group_on_button_click(
     focus.0 = Some(group_id);
     focus_visible.0 = false;
 
+    if read_only {
+        // The selection of a read-only group can't be changed.
+        trigger.propagate(false);
+        return;
+    }
+
     // Get all the radio group children.
     let radio_children = group_children
         .iter()
@@ -166,18 +222,34 @@ fn radio_group_on_button_click(
     }
 
     // Trigger the on_change event for the newly checked radio button
-    if let Some(on_change) = on_change {
-        commands.run_system_with(*on_change, radio_id);
-    } else {
-        commands.trigger_targets(ValueChange(radio_id), group_id);
+    if uncontrolled {
+        check_radio(
+            &mut commands,
+            group_children
+                .iter()
+                .filter(|child| q_radio.contains(*child)),
+            radio_id,
+        );
     }
+    on_change.notify(&mut commands, group_id, radio_id, ValueChange);
+    report_widget_edit(
+        &mut commands,
+        edit_hook.as_deref(),
+        WidgetEdit {
+            entity: group_id,
+            before: WidgetValue::Selection(current_radio),
+            after: WidgetValue::Selection(Some(radio_id)),
+        },
+    );
 }
 
 pub struct CoreRadioGroupPlugin;
 
 impl Plugin for CoreRadioGroupPlugin {
     fn build(&self, app: &mut App) {
+        InteractionStatesPlugin::add_once(app);
         app.add_observer(radio_group_on_key_input)
-            .add_observer(radio_group_on_button_click);
+            .add_observer(radio_group_on_button_click)
+            .register_type::<CoreRadioGroup>();
     }
 }
@@ -1,6 +1,9 @@
 use bevy::prelude::*;
 
-#[derive(Debug, Default, Clone, Copy, PartialEq)]
+use crate::{events::ValueCommit, interaction_states::InteractionStatesPlugin, InteractionState};
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Debug, Default, PartialEq)]
 pub enum Orientation {
     Horizontal,
     #[default]
@@ -16,10 +19,17 @@ pub enum Orientation {
 ///
 /// A scrollbar can have any number of child entities, but one entity must be the scrollbar
 /// thumb, which is marked with the [`CoreScrollbarThumb`] component. Other children are ignored.
-#[derive(Component, Debug)]
-#[require(ScrollbarDragState)]
+///
+/// Dragging the thumb updates the [`ScrollPosition`] of the target entity directly. When the
+/// drag ends, the scrollbar emits a [`ValueCommit<f32>`] event containing the final scroll
+/// offset along its axis. Pressing `Escape` during the drag, or the pointer being cancelled,
+/// aborts the drag and restores the scroll position from before it started.
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component, Debug)]
+#[require(ScrollbarDragState, InteractionState)]
 pub struct CoreScrollbar {
     /// Entity being scrolled.
+    #[entities]
     pub target: Entity,
     /// Whether the scrollbar is vertical or horizontal.
     pub orientation: Orientation,
@@ -29,7 +39,8 @@ pub struct CoreScrollbar {
 
 /// Marker component to indicate that the entity is a scrollbar thumb. This should be a child
 /// of the scrollbar entity.
-#[derive(Component, Debug)]
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component, Debug)]
 pub struct CoreScrollbarThumb;
 
 impl CoreScrollbar {
@@ -43,10 +54,11 @@ impl CoreScrollbar {
 }
 
 /// Component used to manage the state of a scrollbar during dragging.
-#[derive(Component, Default)]
+#[derive(Component, Default, Reflect)]
+#[reflect(Component, Default)]
 pub struct ScrollbarDragState {
     /// Whether the scrollbar is currently being dragged.
-    dragging: bool,
+    pub(crate) dragging: bool,
     /// The value of the scrollbar when dragging started.
     offset: f32,
 }
@@ -158,11 +170,65 @@ pub(crate) fn scrollbar_on_drag(
 pub(crate) fn scrollbar_on_drag_end(
     mut trigger: Trigger<Pointer<DragEnd>>,
     mut q_scrollbar: Query<(&CoreScrollbar, &mut ScrollbarDragState)>,
+    q_scroll_area: Query<&ScrollPosition>,
+    mut commands: Commands,
 ) {
-    if let Ok((_scrollbar, mut drag)) = q_scrollbar.get_mut(trigger.target()) {
+    if let Ok((scrollbar, mut drag)) = q_scrollbar.get_mut(trigger.target()) {
         trigger.propagate(false);
         if drag.dragging {
             drag.dragging = false;
+            if let Ok(scroll_area) = q_scroll_area.get(scrollbar.target) {
+                let offset = match scrollbar.orientation {
+                    Orientation::Horizontal => scroll_area.offset_x,
+                    Orientation::Vertical => scroll_area.offset_y,
+                };
+                commands.trigger_targets(ValueCommit(offset), trigger.target());
+            }
+        }
+    }
+}
+
+/// Abort a scrollbar drag, restoring the scroll position from before the drag started.
+fn cancel_scrollbar_drag(
+    scrollbar: &CoreScrollbar,
+    drag: &mut ScrollbarDragState,
+    q_scroll_pos: &mut Query<&mut ScrollPosition>,
+) {
+    drag.dragging = false;
+    if let Ok(mut scroll_pos) = q_scroll_pos.get_mut(scrollbar.target) {
+        match scrollbar.orientation {
+            Orientation::Horizontal => scroll_pos.offset_x = drag.offset,
+            Orientation::Vertical => scroll_pos.offset_y = drag.offset,
+        }
+    }
+}
+
+pub(crate) fn scrollbar_on_pointer_cancel(
+    mut trigger: Trigger<Pointer<Cancel>>,
+    mut q_scrollbar: Query<(&CoreScrollbar, &mut ScrollbarDragState)>,
+    mut q_scroll_pos: Query<&mut ScrollPosition>,
+) {
+    if let Ok((scrollbar, mut drag)) = q_scrollbar.get_mut(trigger.target()) {
+        trigger.propagate(false);
+        if drag.dragging {
+            cancel_scrollbar_drag(scrollbar, &mut drag, &mut q_scroll_pos);
+        }
+    }
+}
+
+/// System which cancels any scrollbar drag in progress when `Escape` is pressed. Scrollbars
+/// can't have keyboard focus, so this doesn't use the focused input events.
+fn scrollbar_cancel_on_escape(
+    keys: Res<ButtonInput<KeyCode>>,
+    mut q_scrollbar: Query<(&CoreScrollbar, &mut ScrollbarDragState)>,
+    mut q_scroll_pos: Query<&mut ScrollPosition>,
+) {
+    if !keys.just_pressed(KeyCode::Escape) {
+        return;
+    }
+    for (scrollbar, mut drag) in q_scrollbar.iter_mut() {
+        if drag.dragging {
+            cancel_scrollbar_drag(scrollbar, &mut drag, &mut q_scroll_pos);
         }
     }
 }
@@ -241,10 +307,16 @@ pub struct CoreScrollbarPlugin;
 
 impl Plugin for CoreScrollbarPlugin {
     fn build(&self, app: &mut App) {
+        InteractionStatesPlugin::add_once(app);
         app.add_observer(scrollbar_on_pointer_down)
             .add_observer(scrollbar_on_drag_start)
             .add_observer(scrollbar_on_drag_end)
             .add_observer(scrollbar_on_drag)
-            .add_systems(PostUpdate, update_scrollbar_thumb);
+            .add_observer(scrollbar_on_pointer_cancel)
+            .add_systems(Update, scrollbar_cancel_on_escape)
+            .add_systems(PostUpdate, update_scrollbar_thumb)
+            .register_type::<CoreScrollbar>()
+            .register_type::<CoreScrollbarThumb>()
+            .register_type::<ScrollbarDragState>();
     }
 }
@@ -0,0 +1,508 @@
+use bevy::{
+    ecs::{component::HookContext, world::DeferredWorld},
+    input::{keyboard::KeyboardInput, mouse::AccumulatedMouseMotion, ButtonState},
+    input_focus::{FocusedInput, InputFocus, InputFocusVisible},
+    picking::pointer::PointerId,
+    prelude::*,
+    window::{CursorGrabMode, PrimaryWindow},
+};
+
+use crate::{
+    core_slider::clamp_value,
+    core_text_input::TextInputDragState,
+    edit_history::{report_widget_edit, WidgetEdit, WidgetEditHook, WidgetValue},
+    events::ValueCommit,
+    interaction_states::InteractionStatesPlugin,
+    Callback, CoreSpinBox, EffectivelyDisabled, InteractionReadOnly, TextBuffer, Uncontrolled,
+    ValueChange,
+};
+
+/// Headless numeric field which changes its value when dragged horizontally, as in many 3D
+/// editors. The value, range, increment and `on_change` callback come from the [`CoreSpinBox`]
+/// which the scrub field requires, so the field also supports stepping with the arrow keys and
+/// typed entry.
+///
+/// Pressing on the field and moving the pointer further than `drag_threshold` logical pixels
+/// starts scrubbing, and the value changes by `sensitivity` for each pixel of horizontal motion.
+/// Holding `Shift` makes the change ten times finer, and holding `Ctrl` snaps the value to
+/// multiples of the spin box's `increment`. Only the pointer which pressed the field scrubs it,
+/// so a touch works in the same way as the mouse.
+///
+/// When scrubbing with the mouse, the pointer is locked and hidden, and the value follows the raw
+/// mouse motion. If `wrap_cursor` is true, the pointer is instead kept visible and wraps around to
+/// the other side of the window when it reaches an edge, so that the drag is never limited by the
+/// size of the window.
+///
+/// While scrubbing, the new value is reported on every frame in the same way as other spin box
+/// changes. When the pointer is released, the final value is reported via the `on_commit`
+/// callback, which by default emits a [`ValueCommit<f32>`] event. Typed values are committed in
+/// the same way.
+///
+/// Pressing `Escape` while scrubbing, or losing the pointer (for example when a touch is
+/// cancelled), cancels the drag: the value from before the drag is reported as a change, and
+/// nothing is committed.
+///
+/// Releasing the pointer without moving it past the threshold makes the text editable, with all of
+/// the text selected. Editing ends when the text is committed or the field loses focus.
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component, Debug, Default)]
+#[require(CoreSpinBox, ScrubState)]
+#[component(on_add = on_add_scrub_field)]
+pub struct CoreScrubField {
+    /// The change in value per pixel of pointer motion.
+    pub sensitivity: f32,
+    /// How far, in logical pixels, the pointer must move before a press becomes a drag.
+    pub drag_threshold: f32,
+    /// Wrap the pointer around the edges of the window while scrubbing, instead of locking it.
+    pub wrap_cursor: bool,
+    #[reflect(ignore)]
+    pub on_commit: Callback<f32>,
+}
+
+impl Default for CoreScrubField {
+    fn default() -> Self {
+        Self {
+            sensitivity: 1.0,
+            drag_threshold: 3.0,
+            wrap_cursor: false,
+            on_commit: Callback::Event,
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+enum ScrubPhase {
+    #[default]
+    Idle,
+    /// The pointer has been pressed, but hasn't yet moved far enough to start scrubbing.
+    Pressed,
+    Scrubbing,
+    /// The text is being edited.
+    Editing,
+}
+
+/// Component used to manage the state of a scrub field during dragging.
+#[derive(Component, Debug, Default)]
+pub(crate) struct ScrubState {
+    phase: ScrubPhase,
+    /// The pointer which pressed the field.
+    pointer: Option<PointerId>,
+    /// The value when scrubbing started.
+    start_value: f32,
+    /// The scrubbed value before snapping.
+    raw_value: f32,
+}
+
+// Hook to stop the text input from handling the pointer until editing starts.
+fn on_add_scrub_field(mut world: DeferredWorld, context: HookContext) {
+    if let Some(mut drag) = world.get_mut::<TextInputDragState>(context.entity) {
+        drag.pointer_disabled = true;
+    }
+}
+
+/// Report that the user has finished changing the value.
+fn emit_scrub_commit(commands: &mut Commands, entity: Entity, field: &CoreScrubField, value: f32) {
+    field.on_commit.notify(commands, entity, value, ValueCommit);
+}
+
+/// Change the value by `delta` pixels of horizontal pointer motion.
+#[allow(clippy::too_many_arguments)]
+fn scrub_by(
+    commands: &mut Commands,
+    entity: Entity,
+    field: &CoreScrubField,
+    spin_box: &mut Mut<CoreSpinBox>,
+    state: &mut ScrubState,
+    uncontrolled: bool,
+    keys: &ButtonInput<KeyCode>,
+    delta: f32,
+) {
+    let fine = keys.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+    let snap = keys.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]);
+    let speed = if fine { 0.1 } else { 1.0 };
+    state.raw_value = clamp_value(
+        state.raw_value + delta * field.sensitivity * speed,
+        spin_box.min,
+        spin_box.max,
+    );
+    let mut value = state.raw_value;
+    if snap && spin_box.increment > 0. {
+        value = clamp_value(
+            (value / spin_box.increment).round() * spin_box.increment,
+            spin_box.min,
+            spin_box.max,
+        );
+    }
+    if value != spin_box.value {
+        emit_scrub_change(commands, entity, spin_box, uncontrolled, value);
+    }
+}
+
+/// Stop scrubbing, restoring the value from before the drag.
+fn cancel_scrub(
+    commands: &mut Commands,
+    entity: Entity,
+    spin_box: &mut Mut<CoreSpinBox>,
+    state: &mut ScrubState,
+    uncontrolled: bool,
+    q_window: &mut Query<&mut Window, With<PrimaryWindow>>,
+) {
+    state.phase = ScrubPhase::Idle;
+    if let Ok(mut window) = q_window.single_mut() {
+        release_cursor(&mut window);
+    }
+    emit_scrub_change(commands, entity, spin_box, uncontrolled, state.start_value);
+}
+
+/// Report a new value while scrubbing, in the same way as the spin box reports changes.
+fn emit_scrub_change(
+    commands: &mut Commands,
+    entity: Entity,
+    spin_box: &mut Mut<CoreSpinBox>,
+    uncontrolled: bool,
+    value: f32,
+) {
+    if uncontrolled {
+        spin_box.value = value;
+    }
+    spin_box
+        .on_change
+        .notify(commands, entity, value, ValueChange);
+}
+
+#[allow(clippy::type_complexity)]
+fn scrub_on_pointer_down(
+    mut trigger: Trigger<Pointer<Pressed>>,
+    mut q_state: Query<
+        (
+            &mut ScrubState,
+            Has<EffectivelyDisabled>,
+            Has<InteractionReadOnly>,
+        ),
+        With<CoreScrubField>,
+    >,
+    mut focus: ResMut<InputFocus>,
+    mut focus_visible: ResMut<InputFocusVisible>,
+) {
+    let Ok((mut state, disabled, read_only)) = q_state.get_mut(trigger.target()) else {
+        return;
+    };
+    if state.phase == ScrubPhase::Editing {
+        // Let the text input handle the press.
+        return;
+    }
+    trigger.propagate(false);
+    focus.0 = Some(trigger.target());
+    focus_visible.0 = false;
+    if !disabled && !read_only && trigger.event().button == PointerButton::Primary {
+        state.phase = ScrubPhase::Pressed;
+        state.pointer = Some(trigger.pointer_id);
+    }
+}
+
+#[allow(clippy::type_complexity)]
+fn scrub_on_drag(
+    mut trigger: Trigger<Pointer<Drag>>,
+    mut q_state: Query<(
+        &CoreScrubField,
+        &mut CoreSpinBox,
+        &mut ScrubState,
+        Has<Uncontrolled>,
+    )>,
+    mut q_window: Query<&mut Window, With<PrimaryWindow>>,
+    keys: Res<ButtonInput<KeyCode>>,
+    mut commands: Commands,
+) {
+    let entity = trigger.target();
+    let Ok((field, mut spin_box, mut state, uncontrolled)) = q_state.get_mut(entity) else {
+        return;
+    };
+    if state.pointer != Some(trigger.pointer_id) {
+        return;
+    }
+    match state.phase {
+        ScrubPhase::Pressed => {
+            trigger.propagate(false);
+            if trigger.event().distance.length() <= field.drag_threshold {
+                return;
+            }
+            state.phase = ScrubPhase::Scrubbing;
+            state.start_value = spin_box.value;
+            state.raw_value = spin_box.value;
+            if trigger.pointer_id != PointerId::Mouse {
+                return;
+            }
+            if let Ok(mut window) = q_window.single_mut() {
+                if field.wrap_cursor {
+                    window.cursor_options.grab_mode = CursorGrabMode::Confined;
+                } else {
+                    window.cursor_options.grab_mode = CursorGrabMode::Locked;
+                    window.cursor_options.visible = false;
+                }
+            }
+        }
+        ScrubPhase::Scrubbing => {
+            trigger.propagate(false);
+            // The mouse cursor is locked or warped while scrubbing, so the mouse scrubs by its
+            // raw motion instead; see `update_mouse_scrubbing`.
+            if trigger.pointer_id != PointerId::Mouse {
+                let delta = trigger.event().delta.x;
+                scrub_by(
+                    &mut commands,
+                    entity,
+                    field,
+                    &mut spin_box,
+                    &mut state,
+                    uncontrolled,
+                    &keys,
+                    delta,
+                );
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Commit the scrubbed value when the pointer is released.
+fn scrub_on_drag_end(
+    mut trigger: Trigger<Pointer<DragEnd>>,
+    mut q_state: Query<(&CoreScrubField, &CoreSpinBox, &mut ScrubState)>,
+    mut q_window: Query<&mut Window, With<PrimaryWindow>>,
+    edit_hook: Option<Res<WidgetEditHook>>,
+    mut commands: Commands,
+) {
+    let entity = trigger.target();
+    let Ok((field, spin_box, mut state)) = q_state.get_mut(entity) else {
+        return;
+    };
+    if state.pointer != Some(trigger.pointer_id) {
+        return;
+    }
+    match state.phase {
+        ScrubPhase::Pressed => {
+            trigger.propagate(false);
+            state.phase = ScrubPhase::Idle;
+        }
+        ScrubPhase::Scrubbing => {
+            trigger.propagate(false);
+            state.phase = ScrubPhase::Idle;
+            if let Ok(mut window) = q_window.single_mut() {
+                release_cursor(&mut window);
+            }
+            emit_scrub_commit(&mut commands, entity, field, spin_box.value);
+            report_widget_edit(
+                &mut commands,
+                edit_hook.as_deref(),
+                WidgetEdit {
+                    entity,
+                    before: WidgetValue::Number(state.start_value),
+                    after: WidgetValue::Number(spin_box.value),
+                },
+            );
+        }
+        _ => {}
+    }
+}
+
+/// Cancel scrubbing if the pointer which is scrubbing is lost.
+fn scrub_on_pointer_cancel(
+    mut trigger: Trigger<Pointer<Cancel>>,
+    mut q_state: Query<(&mut CoreSpinBox, &mut ScrubState, Has<Uncontrolled>)>,
+    mut q_window: Query<&mut Window, With<PrimaryWindow>>,
+    mut commands: Commands,
+) {
+    let entity = trigger.target();
+    let Ok((mut spin_box, mut state, uncontrolled)) = q_state.get_mut(entity) else {
+        return;
+    };
+    if state.pointer != Some(trigger.pointer_id) {
+        return;
+    }
+    match state.phase {
+        ScrubPhase::Pressed => {
+            trigger.propagate(false);
+            state.phase = ScrubPhase::Idle;
+        }
+        ScrubPhase::Scrubbing => {
+            trigger.propagate(false);
+            cancel_scrub(
+                &mut commands,
+                entity,
+                &mut spin_box,
+                &mut state,
+                uncontrolled,
+                &mut q_window,
+            );
+        }
+        _ => {}
+    }
+}
+
+fn scrub_on_pointer_click(
+    mut trigger: Trigger<Pointer<Click>>,
+    mut q_state: Query<(&mut ScrubState, &mut TextInputDragState, &mut TextBuffer)>,
+) {
+    let Ok((mut state, mut drag, mut buffer)) = q_state.get_mut(trigger.target()) else {
+        return;
+    };
+    if state.phase != ScrubPhase::Pressed {
+        return;
+    }
+    trigger.propagate(false);
+    state.phase = ScrubPhase::Editing;
+    drag.pointer_disabled = false;
+    buffer.select_all();
+}
+
+/// System which changes the value of a scrub field while it is being scrubbed with the mouse.
+/// Since the cursor is locked, or warped when it wraps around the window, the value follows the
+/// raw mouse motion rather than the pointer position.
+fn update_mouse_scrubbing(
+    mut q_state: Query<(
+        Entity,
+        &CoreScrubField,
+        &mut CoreSpinBox,
+        &mut ScrubState,
+        Has<Uncontrolled>,
+    )>,
+    mut q_window: Query<&mut Window, With<PrimaryWindow>>,
+    motion: Res<AccumulatedMouseMotion>,
+    keys: Res<ButtonInput<KeyCode>>,
+    mut commands: Commands,
+) {
+    for (entity, field, mut spin_box, mut state, uncontrolled) in q_state.iter_mut() {
+        if state.phase != ScrubPhase::Scrubbing || state.pointer != Some(PointerId::Mouse) {
+            continue;
+        }
+        if motion.delta.x != 0. {
+            scrub_by(
+                &mut commands,
+                entity,
+                field,
+                &mut spin_box,
+                &mut state,
+                uncontrolled,
+                &keys,
+                motion.delta.x,
+            );
+        }
+        if field.wrap_cursor {
+            if let Ok(mut window) = q_window.single_mut() {
+                wrap_cursor(&mut window);
+            }
+        }
+    }
+}
+
+/// Undo the cursor grab made when scrubbing started.
+fn release_cursor(window: &mut Window) {
+    window.cursor_options.grab_mode = CursorGrabMode::None;
+    window.cursor_options.visible = true;
+}
+
+/// Cancel scrubbing when `Escape` is pressed, restoring the value from before the drag.
+fn scrub_on_key_input(
+    mut trigger: Trigger<FocusedInput<KeyboardInput>>,
+    mut q_state: Query<
+        (&mut CoreSpinBox, &mut ScrubState, Has<Uncontrolled>),
+        With<CoreScrubField>,
+    >,
+    mut q_window: Query<&mut Window, With<PrimaryWindow>>,
+    mut commands: Commands,
+) {
+    let Ok((mut spin_box, mut state, uncontrolled)) = q_state.get_mut(trigger.target()) else {
+        return;
+    };
+    let event = &trigger.event().input;
+    if state.phase != ScrubPhase::Scrubbing || event.key_code != KeyCode::Escape {
+        return;
+    }
+    let pressed = event.state == ButtonState::Pressed;
+    trigger.propagate(false);
+    if pressed {
+        cancel_scrub(
+            &mut commands,
+            trigger.target(),
+            &mut spin_box,
+            &mut state,
+            uncontrolled,
+            &mut q_window,
+        );
+    }
+}
+
+/// Move the cursor to the opposite side of the window if it is at an edge.
+fn wrap_cursor(window: &mut Window) {
+    let Some(position) = window.cursor_position() else {
+        return;
+    };
+    let size = window.size();
+    let mut wrapped = position;
+    for axis in 0..2 {
+        if position[axis] <= 1. {
+            wrapped[axis] = size[axis] - 2.;
+        } else if position[axis] >= size[axis] - 1. {
+            wrapped[axis] = 2.;
+        }
+    }
+    if wrapped != position {
+        window.set_cursor_position(Some(wrapped));
+    }
+}
+
+/// Report typed values as commits, and stop editing.
+fn scrub_on_text_commit(
+    trigger: Trigger<ValueCommit<String>>,
+    mut q_state: Query<(
+        &CoreScrubField,
+        &CoreSpinBox,
+        &mut ScrubState,
+        &mut TextInputDragState,
+    )>,
+    mut commands: Commands,
+) {
+    let entity = trigger.target();
+    let Ok((field, spin_box, mut state, mut drag)) = q_state.get_mut(entity) else {
+        return;
+    };
+    if state.phase == ScrubPhase::Editing {
+        state.phase = ScrubPhase::Idle;
+        drag.pointer_disabled = true;
+    }
+    if let Some(value) = spin_box.parse_value(&trigger.event().0) {
+        emit_scrub_commit(&mut commands, entity, field, value);
+    }
+}
+
+/// System which stops editing the text of a scrub field when it loses focus.
+fn scrub_end_editing_on_blur(
+    focus: Res<InputFocus>,
+    mut q_state: Query<(Entity, &mut ScrubState, &mut TextInputDragState), With<CoreScrubField>>,
+) {
+    if !focus.is_changed() {
+        return;
+    }
+    for (entity, mut state, mut drag) in q_state.iter_mut() {
+        if state.phase == ScrubPhase::Editing && focus.0 != Some(entity) {
+            state.phase = ScrubPhase::Idle;
+            drag.pointer_disabled = true;
+        }
+    }
+}
+
+pub struct CoreScrubFieldPlugin;
+
+impl Plugin for CoreScrubFieldPlugin {
+    fn build(&self, app: &mut App) {
+        InteractionStatesPlugin::add_once(app);
+        app.add_observer(scrub_on_pointer_down)
+            .add_observer(scrub_on_drag)
+            .add_observer(scrub_on_drag_end)
+            .add_observer(scrub_on_pointer_cancel)
+            .add_observer(scrub_on_pointer_click)
+            .add_observer(scrub_on_key_input)
+            .add_observer(scrub_on_text_commit)
+            .add_systems(Update, (update_mouse_scrubbing, scrub_end_editing_on_blur))
+            .register_type::<CoreScrubField>();
+    }
+}
@@ -1,21 +1,35 @@
 use accesskit::{Orientation, Role};
 use bevy::{
     a11y::AccessibilityNode,
-    ecs::system::SystemId,
     input::{keyboard::KeyboardInput, ButtonState},
     input_focus::{FocusedInput, InputFocus, InputFocusVisible},
     prelude::*,
 };
 
-use crate::{InteractionDisabled, ValueChange};
+use crate::{
+    edit_history::{report_widget_edit, WidgetEdit, WidgetEditHook, WidgetValue},
+    events::ValueCommit,
+    interaction_states::InteractionStatesPlugin,
+    Callback, EffectivelyDisabled, InteractionReadOnly, InteractionState, Uncontrolled,
+    ValueChange,
+};
 
 /// A headless slider widget, which can be used to build custom sliders. This component emits
 /// [`ValueChange`] events when the slider value changes. Note that the value in the event is
 /// unclamped - the reason is that the receiver may want to quantize or otherwise modify the value
 /// before clamping. It is the receiver's responsibility to update the slider's value when
-/// the value change event is received.
-#[derive(Component, Debug)]
-#[require(SliderDragState)]
+/// the value change event is received, unless the slider is [`Uncontrolled`], in which case it
+/// stores the clamped value itself.
+///
+/// While the slider is being dragged, a value change is emitted on every frame, as a live
+/// preview. When the user has finished changing the value - at the end of a drag, or after each
+/// key press - the `on_commit` callback is called with the final value, which by default emits a
+/// [`ValueCommit`] event. Pressing `Escape` during a drag, or the pointer being
+/// cancelled, aborts the drag: a value change is emitted which restores the value from before
+/// the drag, and nothing is committed.
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component, Debug, Default)]
+#[require(SliderDragState, InteractionState)]
 #[require(AccessibilityNode(accesskit::Node::new(Role::Slider)))]
 pub struct CoreSlider {
     pub value: f32,
@@ -23,7 +37,10 @@ pub struct CoreSlider {
     pub max: f32,
     pub increment: f32,
     pub thumb_size: f32,
-    pub on_change: Option<SystemId<In<f32>>>,
+    #[reflect(ignore)]
+    pub on_change: Callback<f32>,
+    #[reflect(ignore)]
+    pub on_commit: Callback<f32>,
 }
 
 impl Default for CoreSlider {
@@ -34,7 +51,8 @@ impl Default for CoreSlider {
             max: 1.0,
             increment: 1.0,
             thumb_size: 0.0,
-            on_change: None,
+            on_change: Callback::Event,
+            on_commit: Callback::Event,
         }
     }
 }
@@ -47,7 +65,7 @@ impl CoreSlider {
 
     /// Set the value of the slider, clamping it to the min and max values.
     pub fn set_value(&mut self, value: f32) {
-        self.value = value.clamp(self.min, self.max);
+        self.value = clamp_value(value, self.min, self.max);
     }
 
     /// Set the minimum and maximum value of the slider, clamping the current value to the new
@@ -55,7 +73,7 @@ impl CoreSlider {
     pub fn set_range(&mut self, min: f32, max: f32) {
         self.min = min;
         self.max = max;
-        self.value = self.value.clamp(min, max);
+        self.value = clamp_value(self.value, min, max);
     }
 
     /// Compute the position of the thumb on the slider, as a value between 0 and 1.
@@ -68,8 +86,40 @@ impl CoreSlider {
     }
 }
 
+/// Clamp a value to the range `min..=max`. This is shared by all of the numeric widgets, so that
+/// they agree on how values are limited. Unlike [`f32::clamp`], this doesn't panic if `min` is
+/// greater than `max`; `min` takes precedence.
+pub(crate) fn clamp_value(value: f32, min: f32, max: f32) -> f32 {
+    value.min(max).max(min)
+}
+
+/// Report a new value for the slider via the `on_change` callback, and store it if the slider is
+/// uncontrolled.
+fn emit_slider_change(
+    commands: &mut Commands,
+    entity: Entity,
+    slider: &mut Mut<CoreSlider>,
+    uncontrolled: bool,
+    value: f32,
+) {
+    if uncontrolled {
+        slider.set_value(value);
+    }
+    slider
+        .on_change
+        .notify(commands, entity, value, ValueChange);
+}
+
+/// Report the final value of the slider via the `on_commit` callback.
+fn emit_slider_commit(commands: &mut Commands, entity: Entity, slider: &CoreSlider, value: f32) {
+    slider
+        .on_commit
+        .notify(commands, entity, value, ValueCommit);
+}
+
 /// Component used to manage the state of a slider during dragging.
-#[derive(Component, Default)]
+#[derive(Component, Default, Reflect)]
+#[reflect(Component, Default)]
 pub struct SliderDragState {
     /// Whether the slider is currently being dragged.
     pub dragging: bool,
@@ -92,11 +142,16 @@ pub(crate) fn slider_on_pointer_down(
 
 pub(crate) fn slider_on_drag_start(
     mut trigger: Trigger<Pointer<DragStart>>,
-    mut q_state: Query<(&CoreSlider, &mut SliderDragState, Has<InteractionDisabled>)>,
+    mut q_state: Query<(
+        &CoreSlider,
+        &mut SliderDragState,
+        Has<EffectivelyDisabled>,
+        Has<InteractionReadOnly>,
+    )>,
 ) {
-    if let Ok((slider, mut drag, disabled)) = q_state.get_mut(trigger.target()) {
+    if let Ok((slider, mut drag, disabled, read_only)) = q_state.get_mut(trigger.target()) {
         trigger.propagate(false);
-        if !disabled {
+        if !disabled && !read_only {
             drag.dragging = true;
             drag.offset = slider.value;
         }
@@ -105,10 +160,15 @@ pub(crate) fn slider_on_drag_start(
 
 pub(crate) fn slider_on_drag(
     mut trigger: Trigger<Pointer<Drag>>,
-    mut q_state: Query<(&ComputedNode, &CoreSlider, &mut SliderDragState)>,
+    mut q_state: Query<(
+        &ComputedNode,
+        &mut CoreSlider,
+        &mut SliderDragState,
+        Has<Uncontrolled>,
+    )>,
     mut commands: Commands,
 ) {
-    if let Ok((node, slider, drag)) = q_state.get_mut(trigger.target()) {
+    if let Ok((node, mut slider, drag, uncontrolled)) = q_state.get_mut(trigger.target()) {
         trigger.propagate(false);
         if drag.dragging {
             let distance = trigger.event().distance;
@@ -122,11 +182,13 @@ pub(crate) fn slider_on_drag(
                 slider.min + range * 0.5
             };
 
-            if let Some(on_change) = slider.on_change {
-                commands.run_system_with(on_change, new_value);
-            } else {
-                commands.trigger_targets(ValueChange(new_value), trigger.target());
-            }
+            emit_slider_change(
+                &mut commands,
+                trigger.target(),
+                &mut slider,
+                uncontrolled,
+                new_value,
+            );
         }
     }
 }
@@ -134,26 +196,88 @@ pub(crate) fn slider_on_drag(
 pub(crate) fn slider_on_drag_end(
     mut trigger: Trigger<Pointer<DragEnd>>,
     mut q_state: Query<(&CoreSlider, &mut SliderDragState)>,
+    edit_hook: Option<Res<WidgetEditHook>>,
+    mut commands: Commands,
+) {
+    if let Ok((slider, mut drag)) = q_state.get_mut(trigger.target()) {
+        trigger.propagate(false);
+        if drag.dragging {
+            drag.dragging = false;
+            emit_slider_commit(&mut commands, trigger.target(), slider, slider.value);
+            report_widget_edit(
+                &mut commands,
+                edit_hook.as_deref(),
+                WidgetEdit {
+                    entity: trigger.target(),
+                    before: WidgetValue::Number(drag.offset),
+                    after: WidgetValue::Number(slider.value),
+                },
+            );
+        }
+    }
+}
+
+pub(crate) fn slider_on_pointer_cancel(
+    mut trigger: Trigger<Pointer<Cancel>>,
+    mut q_state: Query<(&mut CoreSlider, &mut SliderDragState, Has<Uncontrolled>)>,
+    mut commands: Commands,
 ) {
-    if let Ok((_slider, mut drag)) = q_state.get_mut(trigger.target()) {
+    if let Ok((mut slider, mut drag, uncontrolled)) = q_state.get_mut(trigger.target()) {
         trigger.propagate(false);
         if drag.dragging {
             drag.dragging = false;
+            emit_slider_change(
+                &mut commands,
+                trigger.target(),
+                &mut slider,
+                uncontrolled,
+                drag.offset,
+            );
         }
     }
 }
 
+#[allow(clippy::type_complexity)]
 fn slider_on_key_input(
     mut trigger: Trigger<FocusedInput<KeyboardInput>>,
-    q_state: Query<(&CoreSlider, Has<InteractionDisabled>)>,
+    mut q_state: Query<(
+        &mut CoreSlider,
+        &mut SliderDragState,
+        Has<EffectivelyDisabled>,
+        Has<InteractionReadOnly>,
+        Has<Uncontrolled>,
+    )>,
+    edit_hook: Option<Res<WidgetEditHook>>,
     mut commands: Commands,
 ) {
-    if let Ok((slider, disabled)) = q_state.get(trigger.target()) {
+    if let Ok((mut slider, mut drag, disabled, read_only, uncontrolled)) =
+        q_state.get_mut(trigger.target())
+    {
         let event = &trigger.event().input;
-        if !disabled && event.state == ButtonState::Pressed {
+        if drag.dragging && event.key_code == KeyCode::Escape {
+            // Cancel the drag, restoring the original value.
+            let pressed = event.state == ButtonState::Pressed;
+            trigger.propagate(false);
+            if pressed {
+                drag.dragging = false;
+                emit_slider_change(
+                    &mut commands,
+                    trigger.target(),
+                    &mut slider,
+                    uncontrolled,
+                    drag.offset,
+                );
+            }
+            return;
+        }
+        if !disabled && !read_only && event.state == ButtonState::Pressed {
             let new_value = match event.key_code {
-                KeyCode::ArrowLeft => (slider.value - slider.increment).max(slider.min),
-                KeyCode::ArrowRight => (slider.value + slider.increment).min(slider.max),
+                KeyCode::ArrowLeft => {
+                    clamp_value(slider.value - slider.increment, slider.min, slider.max)
+                }
+                KeyCode::ArrowRight => {
+                    clamp_value(slider.value + slider.increment, slider.min, slider.max)
+                }
                 KeyCode::Home => slider.min,
                 KeyCode::End => slider.max,
                 _ => {
@@ -161,11 +285,24 @@ fn slider_on_key_input(
                 }
             };
             trigger.propagate(false);
-            if let Some(on_change) = slider.on_change {
-                commands.run_system_with(on_change, new_value);
-            } else {
-                commands.trigger_targets(ValueChange(new_value), trigger.target());
-            }
+            let old_value = slider.value;
+            emit_slider_change(
+                &mut commands,
+                trigger.target(),
+                &mut slider,
+                uncontrolled,
+                new_value,
+            );
+            emit_slider_commit(&mut commands, trigger.target(), &slider, new_value);
+            report_widget_edit(
+                &mut commands,
+                edit_hook.as_deref(),
+                WidgetEdit {
+                    entity: trigger.target(),
+                    before: WidgetValue::Number(old_value),
+                    after: WidgetValue::Number(new_value),
+                },
+            );
         }
     }
 }
@@ -184,11 +321,15 @@ pub struct CoreSliderPlugin;
 
 impl Plugin for CoreSliderPlugin {
     fn build(&self, app: &mut App) {
+        InteractionStatesPlugin::add_once(app);
         app.add_observer(slider_on_pointer_down)
             .add_observer(slider_on_drag_start)
             .add_observer(slider_on_drag_end)
             .add_observer(slider_on_drag)
+            .add_observer(slider_on_pointer_cancel)
             .add_observer(slider_on_key_input)
-            .add_systems(PostUpdate, update_slider_a11y);
+            .add_systems(PostUpdate, update_slider_a11y)
+            .register_type::<CoreSlider>()
+            .register_type::<SliderDragState>();
     }
 }
@@ -0,0 +1,445 @@
+use core::time::Duration;
+
+use accesskit::Role;
+use bevy::{
+    a11y::AccessibilityNode,
+    ecs::{component::HookContext, world::DeferredWorld},
+    input::{keyboard::KeyboardInput, ButtonState},
+    input_focus::{FocusedInput, InputFocus, InputFocusVisible},
+    prelude::*,
+    ui::UiSystem,
+};
+
+use crate::{
+    core_slider::clamp_value, core_text_input::update_text_input_display, events::ValueCommit,
+    interaction_states::InteractionStatesPlugin, ButtonPressed, Callback, CoreTextInput,
+    EffectivelyDisabled, InteractionReadOnly, InteractionState, TextBuffer, Uncontrolled,
+    ValueChange,
+};
+
+/// Headless numeric input widget, also known as a spin button. The value can be typed directly,
+/// since the spin box is also a [`CoreTextInput`] (which it requires). The typed text is parsed
+/// when it is committed, by pressing `Enter` or moving focus away; text which isn't a valid
+/// number is discarded, and valid numbers are clamped to the range `min..=max`.
+///
+/// The value can also be stepped by `increment` with the `ArrowUp` and `ArrowDown` keys, by ten
+/// times `increment` with `PageUp` and `PageDown`, or by pressing a [`CoreSpinBoxButton`] inside
+/// the spin box. Holding a spin box button down repeats the step.
+///
+/// The `min`, `max`, `increment` and clamping rules are the same as for [`CoreSlider`]. Like the
+/// slider, the spin box doesn't change its own value unless it is [`Uncontrolled`]: the
+/// `on_change` callback is called with the new value, which by default emits a
+/// [`ValueChange<f32>`] event. Unlike the slider, the new value is always clamped. The spin box's
+/// text is updated to match `value` whenever it changes, unless the user is in the middle of
+/// typing.
+///
+/// Because the spin box handles the text input's commits itself, the `on_commit` callback of its
+/// `CoreTextInput` should be left as [`Callback::Event`].
+///
+/// [`CoreSlider`]: crate::CoreSlider
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component, Debug, Default)]
+#[require(CoreTextInput)]
+#[component(on_add = on_add_spin_box)]
+pub struct CoreSpinBox {
+    pub value: f32,
+    pub min: f32,
+    pub max: f32,
+    pub increment: f32,
+    /// The number of decimal places to display. If `None`, the value is displayed with as many
+    /// decimal places as are needed to represent it.
+    pub precision: Option<usize>,
+    #[reflect(ignore)]
+    pub on_change: Callback<f32>,
+}
+
+impl Default for CoreSpinBox {
+    fn default() -> Self {
+        Self {
+            value: 0.0,
+            min: f32::MIN,
+            max: f32::MAX,
+            increment: 1.0,
+            precision: None,
+            on_change: Callback::Event,
+        }
+    }
+}
+
+impl CoreSpinBox {
+    /// Get the current value of the spin box.
+    pub fn value(&self) -> f32 {
+        self.value
+    }
+
+    /// Set the value of the spin box, clamping it to the min and max values.
+    pub fn set_value(&mut self, value: f32) {
+        self.value = clamp_value(value, self.min, self.max);
+    }
+
+    /// Set the minimum and maximum value of the spin box, clamping the current value to the new
+    /// range.
+    pub fn set_range(&mut self, min: f32, max: f32) {
+        self.min = min;
+        self.max = max;
+        self.value = clamp_value(self.value, min, max);
+    }
+
+    /// Format a value for display, according to `precision`.
+    pub fn format_value(&self, value: f32) -> String {
+        match self.precision {
+            Some(precision) => format!("{value:.precision$}"),
+            None => value.to_string(),
+        }
+    }
+
+    /// Parse typed text into a value, clamped to the min and max values. Returns `None` if the
+    /// text is not a finite number.
+    pub fn parse_value(&self, text: &str) -> Option<f32> {
+        text.trim()
+            .parse::<f32>()
+            .ok()
+            .filter(|value| value.is_finite())
+            .map(|value| clamp_value(value, self.min, self.max))
+    }
+
+    /// The value after stepping by `steps` increments, clamped to the min and max values.
+    fn stepped_value(&self, steps: f32) -> f32 {
+        clamp_value(self.value + self.increment * steps, self.min, self.max)
+    }
+}
+
+/// Which way a [`CoreSpinBoxButton`] steps the value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Debug, PartialEq)]
+pub enum SpinDirection {
+    Increment,
+    Decrement,
+}
+
+/// A button which steps the value of the [`CoreSpinBox`] it is placed in (it can be any
+/// descendant of the spin box entity). Pressing the button steps once; holding it down repeats
+/// the step, after a short delay. The button is not focusable: pressing it focuses the spin box.
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component, Debug)]
+#[require(ButtonPressed, SpinButtonRepeat, InteractionState)]
+pub struct CoreSpinBoxButton {
+    pub direction: SpinDirection,
+}
+
+/// Auto-repeat state of a spin box button which is being held down.
+#[derive(Component, Debug, Default)]
+pub(crate) struct SpinButtonRepeat {
+    /// The time at which the step should next repeat.
+    next_repeat: Duration,
+}
+
+/// How long a spin box button must be held before the step starts repeating.
+const REPEAT_DELAY: Duration = Duration::from_millis(500);
+
+/// Time between repeated steps while a spin box button is held.
+const REPEAT_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Number of increments stepped by `PageUp` and `PageDown`.
+const PAGE_STEPS: f32 = 10.0;
+
+// Hook to set the a11y role, since `CoreTextInput` requires a text input role.
+fn on_add_spin_box(mut world: DeferredWorld, context: HookContext) {
+    let mut entt = world.entity_mut(context.entity);
+    if let Some(mut accessibility) = entt.get_mut::<AccessibilityNode>() {
+        accessibility.set_role(Role::SpinButton);
+    }
+}
+
+/// The spin box state used when changing its value.
+type SpinBoxQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        &'static mut CoreSpinBox,
+        &'static mut TextBuffer,
+        Has<EffectivelyDisabled>,
+        Has<InteractionReadOnly>,
+        Has<Uncontrolled>,
+    ),
+>;
+
+/// Report a new value for the spin box. If the spin box is uncontrolled, the value is stored and
+/// shown in the text; otherwise the text is refreshed by [`update_spin_box_text`] once the app
+/// has accepted, changed or rejected the value, so that it always matches the spin box's value.
+fn emit_spin_box_change(
+    commands: &mut Commands,
+    entity: Entity,
+    spin_box: &mut Mut<CoreSpinBox>,
+    uncontrolled: bool,
+    buffer: &mut TextBuffer,
+    value: f32,
+) {
+    if uncontrolled {
+        buffer.set_text(spin_box.format_value(value));
+    } else {
+        buffer.uncommitted = false;
+        spin_box.set_changed();
+    }
+    if value == spin_box.value {
+        return;
+    }
+    if uncontrolled {
+        spin_box.value = value;
+    }
+    spin_box
+        .on_change
+        .notify(commands, entity, value, ValueChange);
+}
+
+fn spin_box_on_key_input(
+    mut trigger: Trigger<FocusedInput<KeyboardInput>>,
+    mut q_state: SpinBoxQuery,
+    mut commands: Commands,
+) {
+    let entity = trigger.target();
+    let Ok((mut spin_box, mut buffer, disabled, read_only, uncontrolled)) = q_state.get_mut(entity)
+    else {
+        return;
+    };
+    let event = &trigger.event().input;
+    if disabled || read_only || event.state != ButtonState::Pressed || buffer.is_composing() {
+        return;
+    }
+    let steps = match event.key_code {
+        KeyCode::ArrowUp => 1.0,
+        KeyCode::ArrowDown => -1.0,
+        KeyCode::PageUp => PAGE_STEPS,
+        KeyCode::PageDown => -PAGE_STEPS,
+        _ => return,
+    };
+    trigger.propagate(false);
+    let value = spin_box.stepped_value(steps);
+    emit_spin_box_change(
+        &mut commands,
+        entity,
+        &mut spin_box,
+        uncontrolled,
+        &mut buffer,
+        value,
+    );
+}
+
+fn spin_box_on_commit(
+    mut trigger: Trigger<ValueCommit<String>>,
+    mut q_state: SpinBoxQuery,
+    mut commands: Commands,
+) {
+    let entity = trigger.target();
+    let Ok((mut spin_box, mut buffer, _, _, uncontrolled)) = q_state.get_mut(entity) else {
+        return;
+    };
+    trigger.propagate(false);
+    match spin_box.parse_value(&trigger.event().0) {
+        Some(value) => emit_spin_box_change(
+            &mut commands,
+            entity,
+            &mut spin_box,
+            uncontrolled,
+            &mut buffer,
+            value,
+        ),
+        None => buffer.set_text(spin_box.format_value(spin_box.value)),
+    }
+}
+
+/// Find the spin box that a spin box button belongs to.
+fn find_spin_box(
+    button: Entity,
+    q_parents: &Query<&ChildOf>,
+    is_spin_box: impl Fn(Entity) -> bool,
+) -> Option<Entity> {
+    let mut entity = button;
+    while let Ok(child_of) = q_parents.get(entity) {
+        entity = child_of.parent();
+        if is_spin_box(entity) {
+            return Some(entity);
+        }
+    }
+    None
+}
+
+/// Step the spin box that `button` belongs to.
+fn step_from_button(
+    commands: &mut Commands,
+    button: Entity,
+    direction: SpinDirection,
+    q_parents: &Query<&ChildOf>,
+    q_spin_box: &mut SpinBoxQuery,
+) -> Option<Entity> {
+    let spin_box_id = find_spin_box(button, q_parents, |e| q_spin_box.contains(e))?;
+    let Ok((mut spin_box, mut buffer, false, false, uncontrolled)) =
+        q_spin_box.get_mut(spin_box_id)
+    else {
+        return Some(spin_box_id);
+    };
+    let steps = match direction {
+        SpinDirection::Increment => 1.0,
+        SpinDirection::Decrement => -1.0,
+    };
+    let value = spin_box.stepped_value(steps);
+    emit_spin_box_change(
+        commands,
+        spin_box_id,
+        &mut spin_box,
+        uncontrolled,
+        &mut buffer,
+        value,
+    );
+    Some(spin_box_id)
+}
+
+#[allow(clippy::too_many_arguments)]
+fn spin_button_on_pointer_down(
+    mut trigger: Trigger<Pointer<Pressed>>,
+    mut q_button: Query<(
+        &CoreSpinBoxButton,
+        &mut ButtonPressed,
+        &mut SpinButtonRepeat,
+        Has<EffectivelyDisabled>,
+    )>,
+    q_parents: Query<&ChildOf>,
+    mut q_spin_box: SpinBoxQuery,
+    time: Res<Time<Real>>,
+    mut focus: ResMut<InputFocus>,
+    mut focus_visible: ResMut<InputFocusVisible>,
+    mut commands: Commands,
+) {
+    let button = trigger.target();
+    let Ok((spin_button, mut pressed, mut repeat, disabled)) = q_button.get_mut(button) else {
+        return;
+    };
+    // Don't let the spin box's text input handle the press.
+    trigger.propagate(false);
+    if disabled || trigger.event().button != PointerButton::Primary {
+        return;
+    }
+    let direction = spin_button.direction;
+    if let Some(spin_box_id) = step_from_button(
+        &mut commands,
+        button,
+        direction,
+        &q_parents,
+        &mut q_spin_box,
+    ) {
+        focus.0 = Some(spin_box_id);
+        focus_visible.0 = false;
+    }
+    pressed.press(trigger.pointer_id);
+    repeat.next_repeat = time.elapsed() + REPEAT_DELAY;
+}
+
+fn spin_button_on_pointer_up(
+    mut trigger: Trigger<Pointer<Released>>,
+    mut q_button: Query<&mut ButtonPressed, With<CoreSpinBoxButton>>,
+) {
+    if let Ok(mut pressed) = q_button.get_mut(trigger.target()) {
+        trigger.propagate(false);
+        pressed.release(trigger.pointer_id);
+    }
+}
+
+fn spin_button_on_pointer_cancel(
+    mut trigger: Trigger<Pointer<Cancel>>,
+    mut q_button: Query<&mut ButtonPressed, With<CoreSpinBoxButton>>,
+) {
+    if let Ok(mut pressed) = q_button.get_mut(trigger.target()) {
+        trigger.propagate(false);
+        pressed.release(trigger.pointer_id);
+    }
+}
+
+fn spin_button_on_drag_end(
+    mut trigger: Trigger<Pointer<DragEnd>>,
+    mut q_button: Query<&mut ButtonPressed, With<CoreSpinBoxButton>>,
+) {
+    if let Ok(mut pressed) = q_button.get_mut(trigger.target()) {
+        trigger.propagate(false);
+        pressed.release(trigger.pointer_id);
+    }
+}
+
+/// System which repeats the step of spin box buttons which are being held down.
+fn spin_button_auto_repeat(
+    mut q_button: Query<(
+        Entity,
+        &CoreSpinBoxButton,
+        &ButtonPressed,
+        &mut SpinButtonRepeat,
+        Has<EffectivelyDisabled>,
+    )>,
+    q_parents: Query<&ChildOf>,
+    mut q_spin_box: SpinBoxQuery,
+    time: Res<Time<Real>>,
+    mut commands: Commands,
+) {
+    let now = time.elapsed();
+    for (button, spin_button, pressed, mut repeat, disabled) in q_button.iter_mut() {
+        if !pressed.pressed || disabled || now < repeat.next_repeat {
+            continue;
+        }
+        repeat.next_repeat = now + REPEAT_INTERVAL;
+        step_from_button(
+            &mut commands,
+            button,
+            spin_button.direction,
+            &q_parents,
+            &mut q_spin_box,
+        );
+    }
+}
+
+/// System which updates the text of a spin box when its value changes.
+pub(crate) fn update_spin_box_text(mut q_state: Query<(Ref<CoreSpinBox>, &mut TextBuffer)>) {
+    for (spin_box, mut buffer) in q_state.iter_mut() {
+        if !spin_box.is_changed() || buffer.uncommitted {
+            continue;
+        }
+        let text = spin_box.format_value(spin_box.value);
+        if buffer.text() != text {
+            buffer.set_text(text);
+        }
+    }
+}
+
+fn update_spin_box_a11y(
+    mut q_state: Query<(&CoreSpinBox, &mut AccessibilityNode), Changed<CoreSpinBox>>,
+) {
+    for (spin_box, mut node) in q_state.iter_mut() {
+        node.set_numeric_value(spin_box.value.into());
+        node.set_min_numeric_value(spin_box.min.into());
+        node.set_max_numeric_value(spin_box.max.into());
+        node.set_numeric_value_step(spin_box.increment.into());
+    }
+}
+
+pub struct CoreSpinBoxPlugin;
+
+impl Plugin for CoreSpinBoxPlugin {
+    fn build(&self, app: &mut App) {
+        InteractionStatesPlugin::add_once(app);
+        app.add_observer(spin_box_on_key_input)
+            .add_observer(spin_box_on_commit)
+            .add_observer(spin_button_on_pointer_down)
+            .add_observer(spin_button_on_pointer_up)
+            .add_observer(spin_button_on_pointer_cancel)
+            .add_observer(spin_button_on_drag_end)
+            .add_systems(Update, spin_button_auto_repeat)
+            .register_type::<CoreSpinBox>()
+            .register_type::<CoreSpinBoxButton>()
+            .add_systems(
+                PostUpdate,
+                (
+                    update_spin_box_text
+                        .before(update_text_input_display)
+                        .before(UiSystem::Content),
+                    update_spin_box_a11y,
+                ),
+            );
+    }
+}
@@ -0,0 +1,300 @@
+use accesskit::Role;
+use bevy::{
+    a11y::AccessibilityNode,
+    ecs::{component::HookContext, world::DeferredWorld},
+    input::{
+        keyboard::{Key, KeyboardInput},
+        ButtonState,
+    },
+    input_focus::FocusedInput,
+    prelude::*,
+    text::TextLayoutInfo,
+    transform::TransformSystem,
+    ui::UiSystem,
+};
+
+use crate::{
+    clipboard::Clipboard,
+    core_text_input::{
+        apply_edit_key, apply_history_key, edits_text, emit_text_change, emit_text_commit,
+        find_text_display, record_edit_key, EditModifiers, EditOutcome,
+    },
+    edit_history::{EditHistory, TextEditKind},
+    interaction_states::InteractionStatesPlugin,
+    text_layout::{text_origin, TextGeometry},
+    CoreTextInput, EffectivelyDisabled, InteractionReadOnly, TextBuffer,
+};
+
+/// Headless multi-line text editing widget. This extends [`CoreTextInput`] (which it requires)
+/// with support for newlines, and for moving the cursor between lines with the `Up`, `Down`,
+/// `PageUp` and `PageDown` keys. Vertical movement follows the soft-wrapped lines of the laid-out
+/// text, and remembers the horizontal position the cursor started from, so that moving through
+/// short lines doesn't lose the column. `Home` and `End` move to the start and end of the visual
+/// line; with `Ctrl` held they move to the start and end of the text.
+///
+/// `Enter` inserts a newline; `Ctrl+Enter` commits the text.
+///
+/// The text area entity is expected to be a scrolling container (that is, to have a
+/// [`ScrollPosition`]), with the text displayed by a child entity with a [`Text`] component.
+/// Whenever the cursor moves, the container is scrolled to keep the caret in view. A
+/// [`CoreScrollbar`] can target the text area in the same way as any other scrolling container.
+///
+/// [`CoreScrollbar`]: crate::CoreScrollbar
+#[derive(Component, Debug, Default, Reflect)]
+#[reflect(Component, Debug, Default)]
+#[require(CoreTextInput, TextAreaState)]
+#[component(on_add = on_add_text_area)]
+pub struct CoreTextArea;
+
+/// Editing state for a text area which isn't part of the text buffer.
+#[derive(Component, Debug, Default)]
+pub(crate) struct TextAreaState {
+    /// The horizontal position, in text layout coordinates, that vertical cursor movement is
+    /// trying to maintain.
+    preferred_x: Option<f32>,
+}
+
+// Hook to set the a11y role, since `CoreTextInput` requires a single-line role.
+fn on_add_text_area(mut world: DeferredWorld, context: HookContext) {
+    let mut entt = world.entity_mut(context.entity);
+    if let Some(mut accessibility) = entt.get_mut::<AccessibilityNode>() {
+        accessibility.set_role(Role::MultilineTextInput);
+    }
+}
+
+/// Move the cursor up or down by `lines` visual lines. If there is no text layout yet, falls
+/// back to moving between paragraphs, keeping the same character column.
+fn move_vertical(
+    buffer: &mut TextBuffer,
+    state: &mut TextAreaState,
+    geometry: Option<&TextGeometry>,
+    lines: isize,
+    extend: bool,
+) {
+    let cursor = buffer.cursor();
+    let Some(geometry) = geometry.filter(|geometry| !geometry.lines().is_empty()) else {
+        let text = buffer.text();
+        let para_starts: Vec<usize> = core::iter::once(0)
+            .chain(
+                text.chars()
+                    .enumerate()
+                    .filter(|(_, ch)| *ch == '\n')
+                    .map(|(index, _)| index + 1),
+            )
+            .collect();
+        let current = para_starts.partition_point(|start| *start <= cursor) - 1;
+        let column = cursor - para_starts[current];
+        let target = (current as isize + lines).clamp(0, para_starts.len() as isize - 1) as usize;
+        let target_end = para_starts
+            .get(target + 1)
+            .map(|start| start - 1)
+            .unwrap_or(buffer.len_chars());
+        buffer.set_cursor((para_starts[target] + column).min(target_end), extend);
+        return;
+    };
+
+    let line = geometry.line_at(cursor);
+    let x = *state
+        .preferred_x
+        .get_or_insert_with(|| geometry.caret_x(line, cursor));
+    let target = line as isize + lines;
+    let pos = if target < 0 {
+        0
+    } else if target as usize >= geometry.lines().len() {
+        buffer.len_chars()
+    } else {
+        geometry.char_at_x(target as usize, x)
+    };
+    buffer.set_cursor(pos, extend);
+}
+
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
+fn text_area_on_key_input(
+    mut trigger: Trigger<FocusedInput<KeyboardInput>>,
+    mut q_state: Query<
+        (
+            &CoreTextInput,
+            &mut TextBuffer,
+            &mut TextAreaState,
+            &mut EditHistory,
+            &ComputedNode,
+            Has<EffectivelyDisabled>,
+            Has<InteractionReadOnly>,
+        ),
+        With<CoreTextArea>,
+    >,
+    q_children: Query<&Children>,
+    q_text: Query<&TextLayoutInfo, With<Text>>,
+    keys: Res<ButtonInput<KeyCode>>,
+    time: Res<Time<Real>>,
+    mut clipboard: ResMut<Clipboard>,
+    mut commands: Commands,
+) {
+    let entity = trigger.target();
+    let Ok((input, mut buffer, mut state, mut history, node, disabled, read_only)) =
+        q_state.get_mut(entity)
+    else {
+        return;
+    };
+    let event = &trigger.event().input;
+    if disabled || event.state != ButtonState::Pressed || buffer.is_composing() {
+        return;
+    }
+
+    let mods = EditModifiers::from_keys(&keys);
+    if read_only && edits_text(&event.logical_key, mods) {
+        return;
+    }
+    let geometry = find_text_display(entity, &q_children, |e| q_text.contains(e))
+        .and_then(|display| q_text.get(display).ok())
+        .map(|layout| TextGeometry::new(buffer.text(), layout));
+    let page_lines = geometry
+        .as_ref()
+        .and_then(|geometry| geometry.lines().first())
+        .map(|line| {
+            (node.size().y / (line.bottom - line.top).max(1.))
+                .floor()
+                .max(1.) as isize
+        })
+        .unwrap_or(10);
+
+    let outcome = apply_history_key(&mut buffer, &mut history, &event.logical_key, mods);
+    let outcome = match &event.logical_key {
+        _ if outcome != EditOutcome::Ignored => outcome,
+        Key::Enter if mods.command => {
+            if !event.repeat {
+                emit_text_commit(&mut commands, entity, &input.on_commit, &mut buffer);
+            }
+            trigger.propagate(false);
+            return;
+        }
+        Key::Enter => {
+            history.record(&buffer, TextEditKind::Other, time.elapsed());
+            buffer.insert("\n");
+            EditOutcome::Edited
+        }
+        Key::ArrowUp | Key::ArrowDown | Key::PageUp | Key::PageDown => {
+            let lines = match event.logical_key {
+                Key::ArrowUp => -1,
+                Key::ArrowDown => 1,
+                Key::PageUp => -page_lines,
+                _ => page_lines,
+            };
+            move_vertical(
+                &mut buffer,
+                &mut state,
+                geometry.as_ref(),
+                lines,
+                mods.shift,
+            );
+            history.break_merge();
+            trigger.propagate(false);
+            return;
+        }
+        Key::Home | Key::End if !mods.command => {
+            let cursor = buffer.cursor();
+            let pos = match &geometry {
+                Some(geometry) => {
+                    let line = geometry.line_at(cursor);
+                    let x = if event.logical_key == Key::Home {
+                        f32::NEG_INFINITY
+                    } else {
+                        f32::INFINITY
+                    };
+                    geometry.char_at_x(line, x)
+                }
+                None if event.logical_key == Key::Home => 0,
+                None => buffer.len_chars(),
+            };
+            buffer.set_cursor(pos, mods.shift);
+            history.break_merge();
+            EditOutcome::Moved
+        }
+        key => {
+            let before = buffer.clone();
+            let outcome = apply_edit_key(&mut buffer, key, mods, &mut clipboard, true);
+            record_edit_key(&mut history, &before, outcome, key, mods, time.elapsed());
+            outcome
+        }
+    };
+
+    match outcome {
+        EditOutcome::Ignored => {}
+        EditOutcome::Moved => {
+            trigger.propagate(false);
+            state.preferred_x = None;
+        }
+        EditOutcome::Edited => {
+            trigger.propagate(false);
+            state.preferred_x = None;
+            emit_text_change(&mut commands, entity, &input.on_change, buffer.text());
+        }
+    }
+}
+
+/// System which scrolls a text area so that the caret is visible whenever the cursor moves.
+#[allow(clippy::type_complexity)]
+fn text_area_scroll_to_caret(
+    mut q_state: Query<
+        (
+            Entity,
+            &TextBuffer,
+            &ComputedNode,
+            &GlobalTransform,
+            &mut ScrollPosition,
+        ),
+        (With<CoreTextArea>, Changed<TextBuffer>),
+    >,
+    q_children: Query<&Children>,
+    q_text: Query<(&ComputedNode, &GlobalTransform, &TextLayoutInfo), With<Text>>,
+) {
+    for (entity, buffer, node, transform, mut scroll) in q_state.iter_mut() {
+        let Some((text_node, text_transform, layout)) =
+            find_text_display(entity, &q_children, |e| q_text.contains(e))
+                .and_then(|display| q_text.get(display).ok())
+        else {
+            continue;
+        };
+
+        let caret =
+            TextGeometry::new(&buffer.display_text(), layout).caret_rect(buffer.display_cursor());
+        let origin = text_origin(text_node, text_transform);
+        let caret_min = origin + caret.min;
+        let caret_max = origin + caret.max;
+
+        // Visible region of the text area, in physical pixels.
+        let inset = node.content_inset();
+        let view_min = text_origin(node, transform);
+        let view_max = transform.translation().truncate() + node.size() * 0.5
+            - Vec2::new(inset.right, inset.bottom);
+
+        let mut delta = Vec2::ZERO;
+        for axis in 0..2 {
+            if caret_min[axis] < view_min[axis] {
+                delta[axis] = caret_min[axis] - view_min[axis];
+            } else if caret_max[axis] > view_max[axis] {
+                delta[axis] = caret_max[axis] - view_max[axis];
+            }
+        }
+        if delta != Vec2::ZERO {
+            delta *= node.inverse_scale_factor;
+            scroll.offset_x = (scroll.offset_x + delta.x).max(0.);
+            scroll.offset_y = (scroll.offset_y + delta.y).max(0.);
+        }
+    }
+}
+
+pub struct CoreTextAreaPlugin;
+
+impl Plugin for CoreTextAreaPlugin {
+    fn build(&self, app: &mut App) {
+        InteractionStatesPlugin::add_once(app);
+        app.register_type::<CoreTextArea>();
+        app.add_observer(text_area_on_key_input).add_systems(
+            PostUpdate,
+            text_area_scroll_to_caret
+                .after(UiSystem::PostLayout)
+                .after(TransformSystem::TransformPropagate),
+        );
+    }
+}
@@ -0,0 +1,737 @@
+use core::time::Duration;
+
+use accesskit::Role;
+use bevy::{
+    a11y::AccessibilityNode,
+    input::{
+        keyboard::{Key, KeyboardInput},
+        ButtonState,
+    },
+    input_focus::{FocusedInput, InputFocus, InputFocusVisible},
+    prelude::*,
+    text::TextLayoutInfo,
+    transform::TransformSystem,
+    ui::UiSystem,
+    window::{Ime, PrimaryWindow},
+};
+
+use crate::{
+    clipboard::Clipboard,
+    edit_history::{EditHistory, TextEditKind},
+    events::ValueCommit,
+    interaction_states::InteractionStatesPlugin,
+    text_layout::{hit_test_text, text_origin, TextGeometry},
+    Callback, CoreTextArea, EffectivelyDisabled, InteractionReadOnly, InteractionState, TextBuffer,
+    ValueChange,
+};
+
+/// Headless single-line text input widget. The text, cursor position and selection are stored
+/// in the [`TextBuffer`] component, which the widget edits in response to keyboard input while
+/// it has focus. Styling code can read the cursor and selection ranges from the buffer in order
+/// to draw a caret and selection highlight. If the entity also has a [`Text`] component (or, if
+/// not, one of its children does), that text is kept in sync with the contents of the buffer.
+///
+/// While the text input has focus, input method (IME) editing is enabled for the window, and the
+/// IME candidate window is placed at the caret. Text which is being composed is stored as the
+/// buffer's preedit text, which is displayed at the cursor but not included in the value of the
+/// text input until the composition is committed.
+///
+/// Clicking places the cursor, and dragging selects text; double-clicking selects a word, and
+/// triple-clicking selects a whole line. Dragging past the edge of the text input scrolls it, by
+/// updating its [`ScrollPosition`]; for this to be visible, the node's [`Overflow`] must be set
+/// to scroll. `Ctrl+C`, `Ctrl+X` and `Ctrl+V` copy, cut and paste using the [`Clipboard`]
+/// resource. Edits are recorded in the [`EditHistory`] component: `Ctrl+Z` undoes them, and
+/// `Ctrl+Shift+Z` or `Ctrl+Y` redoes them.
+///
+/// The `on_change` callback will be called whenever the text is edited; by default, the widget
+/// will emit a [`ValueChange<String>`] event. The `on_commit` callback will be called when the
+/// user presses `Enter`, or when the widget loses focus after having been edited; by default, a
+/// [`ValueCommit<String>`] event is emitted.
+#[derive(Component, Debug, Default, Reflect)]
+#[reflect(Component, Debug, Default)]
+#[require(AccessibilityNode(accesskit::Node::new(Role::TextInput)), TextBuffer)]
+#[require(TextInputDragState, EditHistory, ScrollPosition, InteractionState)]
+pub struct CoreTextInput {
+    #[reflect(ignore)]
+    pub on_change: Callback<String>,
+    #[reflect(ignore)]
+    pub on_commit: Callback<String>,
+}
+
+/// The state of the modifier keys, as far as text editing is concerned.
+#[derive(Debug, Default, Clone, Copy)]
+pub(crate) struct EditModifiers {
+    pub shift: bool,
+    /// Control on most platforms, Command on macOS.
+    pub command: bool,
+    pub alt: bool,
+}
+
+impl EditModifiers {
+    pub fn from_keys(keys: &ButtonInput<KeyCode>) -> Self {
+        Self {
+            shift: keys.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]),
+            command: keys.any_pressed([
+                KeyCode::ControlLeft,
+                KeyCode::ControlRight,
+                KeyCode::SuperLeft,
+                KeyCode::SuperRight,
+            ]),
+            alt: keys.any_pressed([KeyCode::AltLeft, KeyCode::AltRight]),
+        }
+    }
+}
+
+/// The result of applying a key press to a [`TextBuffer`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum EditOutcome {
+    /// The key was not an editing key.
+    Ignored,
+    /// The cursor or selection moved, but the text is unchanged.
+    Moved,
+    /// The text was changed.
+    Edited,
+}
+
+/// Returns true if `key` would change the text of a text widget, rather than only moving the
+/// cursor or copying. These keys are rejected by read-only widgets.
+pub(crate) fn edits_text(key: &Key, mods: EditModifiers) -> bool {
+    match key {
+        Key::Character(text) if mods.command => {
+            matches!(text.to_ascii_lowercase().as_str(), "x" | "v" | "z" | "y")
+        }
+        Key::Character(_) | Key::Space | Key::Backspace | Key::Delete | Key::Enter => true,
+        _ => false,
+    }
+}
+
+/// Apply a key press to a text buffer, handling character insertion, deletion, cursor
+/// movement, and copying and pasting via the clipboard. Keys which are specific to the kind of
+/// widget, such as `Enter`, are not handled here. If `multiline` is false, newlines in pasted
+/// text are replaced with spaces.
+pub(crate) fn apply_edit_key(
+    buffer: &mut TextBuffer,
+    key: &Key,
+    mods: EditModifiers,
+    clipboard: &mut Clipboard,
+    multiline: bool,
+) -> EditOutcome {
+    // Word-wise movement uses Control on most platforms, and Alt on macOS.
+    let word = mods.command || mods.alt;
+    match key {
+        Key::Character(text) if mods.command => match text.to_ascii_lowercase().as_str() {
+            "a" => {
+                buffer.select_all();
+                EditOutcome::Moved
+            }
+            "c" => {
+                if buffer.has_selection() {
+                    clipboard.set_text(buffer.selected_text());
+                }
+                EditOutcome::Moved
+            }
+            "x" => {
+                if !buffer.has_selection() {
+                    return EditOutcome::Moved;
+                }
+                clipboard.set_text(buffer.selected_text());
+                buffer.delete_selection();
+                EditOutcome::Edited
+            }
+            "v" => match clipboard.get_text() {
+                Some(text) if !text.is_empty() => {
+                    if multiline {
+                        buffer.insert(&text.replace("\r\n", "\n"));
+                    } else {
+                        buffer.insert(&text.replace(['\r', '\n'], " "));
+                    }
+                    EditOutcome::Edited
+                }
+                _ => EditOutcome::Moved,
+            },
+            _ => EditOutcome::Ignored,
+        },
+        Key::Character(text) => {
+            buffer.insert(text);
+            EditOutcome::Edited
+        }
+        Key::Space => {
+            buffer.insert(" ");
+            EditOutcome::Edited
+        }
+        Key::Backspace => match buffer.delete_backward(word) {
+            true => EditOutcome::Edited,
+            false => EditOutcome::Moved,
+        },
+        Key::Delete => match buffer.delete_forward(word) {
+            true => EditOutcome::Edited,
+            false => EditOutcome::Moved,
+        },
+        Key::ArrowLeft => {
+            buffer.move_left(mods.shift, word);
+            EditOutcome::Moved
+        }
+        Key::ArrowRight => {
+            buffer.move_right(mods.shift, word);
+            EditOutcome::Moved
+        }
+        Key::Home => {
+            buffer.move_home(mods.shift);
+            EditOutcome::Moved
+        }
+        Key::End => {
+            buffer.move_end(mods.shift);
+            EditOutcome::Moved
+        }
+        _ => EditOutcome::Ignored,
+    }
+}
+
+/// Apply an undo or redo key press (`Ctrl+Z`, `Ctrl+Shift+Z` or `Ctrl+Y`) to a text buffer.
+pub(crate) fn apply_history_key(
+    buffer: &mut TextBuffer,
+    history: &mut EditHistory,
+    key: &Key,
+    mods: EditModifiers,
+) -> EditOutcome {
+    let Key::Character(text) = key else {
+        return EditOutcome::Ignored;
+    };
+    if !mods.command {
+        return EditOutcome::Ignored;
+    }
+    let changed = match text.to_ascii_lowercase().as_str() {
+        "z" if mods.shift => history.redo(buffer),
+        "z" => history.undo(buffer),
+        "y" => history.redo(buffer),
+        _ => return EditOutcome::Ignored,
+    };
+    match changed {
+        true => EditOutcome::Edited,
+        false => EditOutcome::Moved,
+    }
+}
+
+/// Update the edit history of a text widget after `key` has been applied to its buffer. `before`
+/// is the state of the buffer before the key was applied.
+pub(crate) fn record_edit_key(
+    history: &mut EditHistory,
+    before: &TextBuffer,
+    outcome: EditOutcome,
+    key: &Key,
+    mods: EditModifiers,
+    now: Duration,
+) {
+    match outcome {
+        EditOutcome::Ignored => {}
+        EditOutcome::Moved => history.break_merge(),
+        EditOutcome::Edited => {
+            let kind = match key {
+                Key::Character(_) | Key::Space if !mods.command => TextEditKind::Insert,
+                Key::Backspace | Key::Delete if !(mods.command || mods.alt) => TextEditKind::Delete,
+                _ => TextEditKind::Other,
+            };
+            history.record(before, kind, now);
+        }
+    }
+}
+
+/// Report an edit to the text via the `on_change` callback.
+pub(crate) fn emit_text_change(
+    commands: &mut Commands,
+    entity: Entity,
+    on_change: &Callback<String>,
+    text: &str,
+) {
+    on_change.notify(commands, entity, text.to_string(), ValueChange);
+}
+
+/// Report that editing is finished via the `on_commit` callback.
+pub(crate) fn emit_text_commit(
+    commands: &mut Commands,
+    entity: Entity,
+    on_commit: &Callback<String>,
+    buffer: &mut TextBuffer,
+) {
+    buffer.uncommitted = false;
+    on_commit.notify(commands, entity, buffer.text().to_string(), ValueCommit);
+}
+
+#[allow(clippy::type_complexity)]
+fn text_input_on_key_input(
+    mut trigger: Trigger<FocusedInput<KeyboardInput>>,
+    mut q_state: Query<
+        (
+            &CoreTextInput,
+            &mut TextBuffer,
+            &mut EditHistory,
+            Has<EffectivelyDisabled>,
+            Has<InteractionReadOnly>,
+        ),
+        Without<CoreTextArea>,
+    >,
+    keys: Res<ButtonInput<KeyCode>>,
+    time: Res<Time<Real>>,
+    mut clipboard: ResMut<Clipboard>,
+    mut commands: Commands,
+) {
+    let entity = trigger.target();
+    let Ok((input, mut buffer, mut history, disabled, read_only)) = q_state.get_mut(entity) else {
+        return;
+    };
+    let event = &trigger.event().input;
+    if disabled || event.state != ButtonState::Pressed || buffer.is_composing() {
+        return;
+    }
+
+    let mods = EditModifiers::from_keys(&keys);
+    if read_only && edits_text(&event.logical_key, mods) {
+        return;
+    }
+
+    if event.logical_key == Key::Enter {
+        if !event.repeat {
+            trigger.propagate(false);
+            emit_text_commit(&mut commands, entity, &input.on_commit, &mut buffer);
+        }
+        return;
+    }
+
+    let mut outcome = apply_history_key(&mut buffer, &mut history, &event.logical_key, mods);
+    if outcome == EditOutcome::Ignored {
+        let before = buffer.clone();
+        outcome = apply_edit_key(&mut buffer, &event.logical_key, mods, &mut clipboard, false);
+        record_edit_key(
+            &mut history,
+            &before,
+            outcome,
+            &event.logical_key,
+            mods,
+            time.elapsed(),
+        );
+    }
+
+    match outcome {
+        EditOutcome::Ignored => {}
+        EditOutcome::Moved => {
+            trigger.propagate(false);
+        }
+        EditOutcome::Edited => {
+            trigger.propagate(false);
+            emit_text_change(&mut commands, entity, &input.on_change, buffer.text());
+        }
+    }
+}
+
+/// System which applies input method events to the focused text input.
+#[allow(clippy::type_complexity)]
+fn text_input_on_ime(
+    mut ime_events: EventReader<Ime>,
+    focus: Res<InputFocus>,
+    mut q_state: Query<(
+        &CoreTextInput,
+        &mut TextBuffer,
+        &mut EditHistory,
+        Has<EffectivelyDisabled>,
+        Has<InteractionReadOnly>,
+    )>,
+    time: Res<Time<Real>>,
+    mut commands: Commands,
+) {
+    for event in ime_events.read() {
+        let Some(entity) = focus.0 else {
+            continue;
+        };
+        let Ok((input, mut buffer, mut history, false, false)) = q_state.get_mut(entity) else {
+            continue;
+        };
+        match event {
+            Ime::Preedit { value, cursor, .. } => {
+                buffer.set_preedit(value.as_str(), *cursor);
+            }
+            Ime::Commit { value, .. } => {
+                buffer.clear_preedit();
+                if !value.is_empty() {
+                    history.record(&buffer, TextEditKind::Other, time.elapsed());
+                    buffer.insert(value);
+                    emit_text_change(&mut commands, entity, &input.on_change, buffer.text());
+                }
+            }
+            Ime::Disabled { .. } => {
+                buffer.clear_preedit();
+            }
+            Ime::Enabled { .. } => {}
+        }
+    }
+}
+
+/// System which enables IME input on the window while a text input has focus, and places the
+/// IME candidate window at the caret.
+#[allow(clippy::type_complexity)]
+fn update_text_input_ime_window(
+    focus: Res<InputFocus>,
+    q_state: Query<
+        (
+            &TextBuffer,
+            Has<EffectivelyDisabled>,
+            Has<InteractionReadOnly>,
+        ),
+        With<CoreTextInput>,
+    >,
+    q_children: Query<&Children>,
+    q_text: Query<(&ComputedNode, &GlobalTransform, &TextLayoutInfo), With<Text>>,
+    mut q_window: Query<&mut Window, With<PrimaryWindow>>,
+    mut ime_active: Local<bool>,
+) {
+    let Ok(mut window) = q_window.single_mut() else {
+        return;
+    };
+    let focused = focus
+        .0
+        .and_then(|entity| Some((entity, q_state.get(entity).ok()?)));
+    match focused {
+        Some((entity, (buffer, false, false))) => {
+            if let Some((node, transform, layout)) =
+                find_text_display(entity, &q_children, |e| q_text.contains(e))
+                    .and_then(|display| q_text.get(display).ok())
+            {
+                let caret = TextGeometry::new(&buffer.display_text(), layout)
+                    .caret_rect(buffer.display_cursor());
+                let position = (text_origin(node, transform) + Vec2::new(caret.min.x, caret.max.y))
+                    * node.inverse_scale_factor;
+                if window.ime_position != position {
+                    window.ime_position = position;
+                }
+            }
+            if !window.ime_enabled {
+                window.ime_enabled = true;
+            }
+            *ime_active = true;
+        }
+        _ if *ime_active => {
+            window.ime_enabled = false;
+            *ime_active = false;
+        }
+        _ => {}
+    }
+}
+
+/// How much text a pointer drag selects at a time, depending on how many times the user clicked
+/// before starting to drag.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+enum SelectGranularity {
+    #[default]
+    Char,
+    Word,
+    Line,
+}
+
+/// Component used to manage the state of a text input during pointer selection.
+#[derive(Component, Debug, Default)]
+pub(crate) struct TextInputDragState {
+    /// Whether a drag selection is in progress.
+    dragging: bool,
+    granularity: SelectGranularity,
+    /// The range that was selected by the initial click, which is always kept selected while
+    /// extending the selection by words or lines.
+    origin: core::ops::Range<usize>,
+    /// Number of consecutive clicks, for detecting double and triple clicks.
+    clicks: u32,
+    last_click_time: Duration,
+    /// The latest pointer position, in logical window coordinates.
+    pointer_position: Vec2,
+    /// If true, pointer presses don't place the cursor or start a selection. This is used by
+    /// widgets which give the pointer a different meaning until the user starts editing.
+    pub(crate) pointer_disabled: bool,
+}
+
+/// Maximum time between clicks for them to count as a double or triple click.
+const MULTI_CLICK_TIME: Duration = Duration::from_millis(500);
+
+/// Maximum distance, in logical pixels, between clicks for them to count as a multi-click.
+const MULTI_CLICK_DISTANCE: f32 = 4.0;
+
+/// Speed of auto-scrolling while dragging outside of a text input, in multiples of the distance
+/// outside the text input per second.
+const AUTO_SCROLL_SPEED: f32 = 10.0;
+
+/// Extend the selection while dragging to include the character index `pos`, in whole units of
+/// the drag granularity.
+fn extend_drag_selection(buffer: &mut TextBuffer, drag: &TextInputDragState, pos: usize) {
+    let unit = match drag.granularity {
+        SelectGranularity::Char => pos..pos,
+        SelectGranularity::Word => buffer.word_at(pos),
+        SelectGranularity::Line => buffer.line_at(pos),
+    };
+    if unit.start < drag.origin.start {
+        buffer.select_range(drag.origin.end..unit.start);
+    } else {
+        buffer.select_range(drag.origin.start..unit.end.max(drag.origin.end));
+    }
+}
+
+/// Query used to find the laid-out text of a text input, for hit testing.
+type TextLayoutQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        &'static ComputedNode,
+        &'static GlobalTransform,
+        &'static TextLayoutInfo,
+    ),
+    With<Text>,
+>;
+
+/// Find the character index in the text input's buffer under the pointer.
+fn pointer_hit_test(
+    entity: Entity,
+    buffer: &TextBuffer,
+    position: Vec2,
+    q_children: &Query<&Children>,
+    q_text: &TextLayoutQuery,
+) -> Option<usize> {
+    let display = find_text_display(entity, q_children, |e| q_text.contains(e))?;
+    let (node, transform, layout) = q_text.get(display).ok()?;
+    // The laid-out glyphs include any preedit text, so hit test against that and map back.
+    let index = hit_test_text(&buffer.display_text(), layout, node, transform, position);
+    Some(buffer.buffer_index(index))
+}
+
+#[allow(clippy::too_many_arguments)]
+fn text_input_on_pointer_down(
+    mut trigger: Trigger<Pointer<Pressed>>,
+    mut q_state: Query<(
+        &mut TextBuffer,
+        &mut TextInputDragState,
+        Has<EffectivelyDisabled>,
+    )>,
+    q_children: Query<&Children>,
+    q_text: TextLayoutQuery,
+    keys: Res<ButtonInput<KeyCode>>,
+    time: Res<Time<Real>>,
+    mut focus: ResMut<InputFocus>,
+    mut focus_visible: ResMut<InputFocusVisible>,
+) {
+    let entity = trigger.target();
+    let Ok((mut buffer, mut drag, disabled)) = q_state.get_mut(entity) else {
+        return;
+    };
+    if drag.pointer_disabled {
+        return;
+    }
+    trigger.propagate(false);
+    focus.0 = Some(entity);
+    focus_visible.0 = false;
+    if disabled || buffer.is_composing() || trigger.event().button != PointerButton::Primary {
+        return;
+    }
+
+    let position = trigger.event().pointer_location.position;
+    let Some(pos) = pointer_hit_test(entity, &buffer, position, &q_children, &q_text) else {
+        return;
+    };
+
+    let now = time.elapsed();
+    if now - drag.last_click_time <= MULTI_CLICK_TIME
+        && drag.pointer_position.distance(position) <= MULTI_CLICK_DISTANCE
+    {
+        drag.clicks = drag.clicks % 3 + 1;
+    } else {
+        drag.clicks = 1;
+    }
+    drag.last_click_time = now;
+    drag.pointer_position = position;
+    drag.dragging = true;
+
+    if drag.clicks == 1 && keys.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]) {
+        // Shift-click extends the existing selection.
+        let anchor = buffer.anchor();
+        drag.granularity = SelectGranularity::Char;
+        drag.origin = anchor..anchor;
+        buffer.set_cursor(pos, true);
+        return;
+    }
+
+    (drag.granularity, drag.origin) = match drag.clicks {
+        1 => (SelectGranularity::Char, pos..pos),
+        2 => (SelectGranularity::Word, buffer.word_at(pos)),
+        _ => (SelectGranularity::Line, buffer.line_at(pos)),
+    };
+    let origin = drag.origin.clone();
+    buffer.select_range(origin);
+}
+
+fn text_input_on_drag(
+    mut trigger: Trigger<Pointer<Drag>>,
+    mut q_state: Query<(&mut TextBuffer, &mut TextInputDragState)>,
+    q_children: Query<&Children>,
+    q_text: TextLayoutQuery,
+) {
+    let entity = trigger.target();
+    let Ok((mut buffer, mut drag)) = q_state.get_mut(entity) else {
+        return;
+    };
+    trigger.propagate(false);
+    if !drag.dragging {
+        return;
+    }
+    drag.pointer_position = trigger.event().pointer_location.position;
+    if let Some(pos) =
+        pointer_hit_test(entity, &buffer, drag.pointer_position, &q_children, &q_text)
+    {
+        extend_drag_selection(&mut buffer, &drag, pos);
+    }
+}
+
+fn text_input_on_drag_end(
+    mut trigger: Trigger<Pointer<DragEnd>>,
+    mut q_state: Query<&mut TextInputDragState>,
+) {
+    if let Ok(mut drag) = q_state.get_mut(trigger.target()) {
+        trigger.propagate(false);
+        drag.dragging = false;
+    }
+}
+
+fn text_input_on_pointer_up(
+    mut trigger: Trigger<Pointer<Released>>,
+    mut q_state: Query<&mut TextInputDragState>,
+) {
+    if let Ok(mut drag) = q_state.get_mut(trigger.target()) {
+        trigger.propagate(false);
+        drag.dragging = false;
+    }
+}
+
+/// System which scrolls a text input while the user is drag-selecting past its edges.
+fn text_input_drag_autoscroll(
+    mut q_state: Query<(
+        Entity,
+        &mut TextBuffer,
+        &TextInputDragState,
+        &ComputedNode,
+        &GlobalTransform,
+        &mut ScrollPosition,
+    )>,
+    q_children: Query<&Children>,
+    q_text: TextLayoutQuery,
+    time: Res<Time<Real>>,
+) {
+    for (entity, mut buffer, drag, node, transform, mut scroll) in q_state.iter_mut() {
+        if !drag.dragging {
+            continue;
+        }
+        // Visible region of the text input, in logical pixels.
+        let inset = node.content_inset();
+        let view_min = text_origin(node, transform) * node.inverse_scale_factor;
+        let view_max = (transform.translation().truncate() + node.size() * 0.5
+            - Vec2::new(inset.right, inset.bottom))
+            * node.inverse_scale_factor;
+        let pointer = drag.pointer_position;
+        let overshoot = pointer - pointer.clamp(view_min, view_max.max(view_min));
+        if overshoot == Vec2::ZERO {
+            continue;
+        }
+
+        let delta = overshoot * AUTO_SCROLL_SPEED * time.delta_secs();
+        scroll.offset_x = (scroll.offset_x + delta.x).max(0.);
+        scroll.offset_y = (scroll.offset_y + delta.y).max(0.);
+        if let Some(pos) = pointer_hit_test(entity, &buffer, pointer, &q_children, &q_text) {
+            extend_drag_selection(&mut buffer, drag, pos);
+        }
+    }
+}
+
+/// System which commits the text of a text input when it loses focus, if it has been edited.
+fn text_input_commit_on_blur(
+    focus: Res<InputFocus>,
+    mut prev_focus: Local<Option<Entity>>,
+    mut q_state: Query<(&CoreTextInput, &mut TextBuffer)>,
+    mut commands: Commands,
+) {
+    if focus.0 == *prev_focus {
+        return;
+    }
+    if let Some(prev) = prev_focus.take() {
+        if let Ok((input, mut buffer)) = q_state.get_mut(prev) {
+            if buffer.uncommitted {
+                emit_text_commit(&mut commands, prev, &input.on_commit, &mut buffer);
+            }
+        }
+    }
+    *prev_focus = focus.0;
+}
+
+/// Find the entity which displays the text of a text widget: the widget itself, if it has a
+/// [`Text`] component, otherwise its first child which does.
+pub(crate) fn find_text_display(
+    entity: Entity,
+    q_children: &Query<&Children>,
+    has_text: impl Fn(Entity) -> bool,
+) -> Option<Entity> {
+    if has_text(entity) {
+        return Some(entity);
+    }
+    q_children
+        .get(entity)
+        .ok()?
+        .iter()
+        .find(|child| has_text(*child))
+}
+
+#[allow(clippy::type_complexity)]
+pub(crate) fn update_text_input_display(
+    mut q_state: Query<
+        (Entity, &TextBuffer, &mut AccessibilityNode),
+        (With<CoreTextInput>, Changed<TextBuffer>),
+    >,
+    q_children: Query<&Children>,
+    mut q_text: Query<&mut Text>,
+) {
+    for (entity, buffer, mut node) in q_state.iter_mut() {
+        node.set_value(buffer.text());
+        let Some(display) = find_text_display(entity, &q_children, |e| q_text.contains(e)) else {
+            continue;
+        };
+        if let Ok(mut text) = q_text.get_mut(display) {
+            let display_text = buffer.display_text();
+            if text.0 != display_text {
+                text.0 = display_text.into_owned();
+            }
+        }
+    }
+}
+
+pub struct CoreTextInputPlugin;
+
+impl Plugin for CoreTextInputPlugin {
+    fn build(&self, app: &mut App) {
+        InteractionStatesPlugin::add_once(app);
+        app.init_resource::<Clipboard>()
+            .register_type::<CoreTextInput>()
+            .register_type::<TextBuffer>()
+            .register_type::<EditHistory>()
+            .add_observer(text_input_on_key_input)
+            .add_observer(text_input_on_pointer_down)
+            .add_observer(text_input_on_drag)
+            .add_observer(text_input_on_drag_end)
+            .add_observer(text_input_on_pointer_up)
+            .add_systems(
+                Update,
+                (
+                    text_input_on_ime,
+                    text_input_commit_on_blur,
+                    text_input_drag_autoscroll,
+                ),
+            )
+            .add_systems(
+                PostUpdate,
+                (
+                    update_text_input_display.before(UiSystem::Content),
+                    update_text_input_ime_window
+                        .after(UiSystem::PostLayout)
+                        .after(TransformSystem::TransformPropagate),
+                ),
+            );
+    }
+}
@@ -0,0 +1,228 @@
+use core::time::Duration;
+
+use accesskit::Role;
+use bevy::{
+    a11y::AccessibilityNode,
+    ecs::{component::HookContext, world::DeferredWorld},
+    input_focus::{InputFocus, InputFocusVisible},
+    prelude::*,
+};
+
+use crate::hover::Hovering;
+
+/// Marker component for a tooltip popup. The tooltip's [`Visibility`] is managed by the
+/// [`CoreTooltipPlugin`]: it is shown when the pointer has rested on its [`TooltipSource`] for
+/// long enough, or when the source has visible keyboard focus, and hidden otherwise. How the
+/// tooltip looks, and where it is placed (for example using a [`CorePopover`]), is up to the app.
+///
+/// [`CorePopover`]: crate::CorePopover
+#[derive(Component, Debug, Default, Reflect)]
+#[reflect(Component, Debug, Default)]
+#[require(Visibility = Visibility::Hidden)]
+#[require(AccessibilityNode(accesskit::Node::new(Role::Tooltip)))]
+pub struct CoreTooltip;
+
+/// Component which is placed on a widget to give it a tooltip. The `tooltip` field is the
+/// entity of the [`CoreTooltip`] popup, and `description` is the text of the tooltip, which is
+/// also used as the a11y description of the widget.
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component, Debug)]
+#[require(Hovering)]
+#[component(immutable, on_add = on_set_description, on_replace = on_set_description)]
+pub struct TooltipSource {
+    #[entities]
+    pub tooltip: Entity,
+    pub description: String,
+}
+
+// Hook to set the a11y description when the tooltip source is added.
+fn on_set_description(mut world: DeferredWorld, context: HookContext) {
+    let mut entt = world.entity_mut(context.entity);
+    let description = entt.get::<TooltipSource>().unwrap().description.clone();
+    if let Some(mut accessibility) = entt.get_mut::<AccessibilityNode>() {
+        accessibility.set_description(description);
+    }
+}
+
+/// Resource which controls the timing of tooltips.
+#[derive(Resource, Debug, Clone, Reflect)]
+#[reflect(Resource, Debug, Default)]
+pub struct TooltipSettings {
+    /// How long the pointer must rest on a widget before its tooltip is shown.
+    pub delay: Duration,
+    /// How long the tooltip stays visible after the pointer leaves the widget.
+    pub grace_period: Duration,
+    /// If another tooltip is requested within this time of a tooltip being hidden, it is shown
+    /// immediately, without waiting for `delay`. This lets the user sweep the pointer along a
+    /// toolbar without waiting for each tooltip.
+    pub skip_delay_window: Duration,
+}
+
+impl Default for TooltipSettings {
+    fn default() -> Self {
+        Self {
+            delay: Duration::from_millis(600),
+            grace_period: Duration::from_millis(100),
+            skip_delay_window: Duration::from_millis(300),
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+enum TooltipPhase {
+    /// No tooltip is shown or pending.
+    #[default]
+    Idle,
+    /// Waiting for the show delay to elapse.
+    Pending { since: Duration },
+    /// The tooltip is visible.
+    Shown,
+    /// The source is no longer engaged; waiting for the grace period to elapse.
+    Closing { since: Duration },
+}
+
+/// Resource which tracks the tooltip that is currently shown, or about to be shown.
+#[derive(Resource, Debug, Default)]
+pub struct TooltipState {
+    source: Option<Entity>,
+    /// The tooltip of `source`, kept so that it can be hidden after the source is despawned.
+    tooltip: Option<Entity>,
+    phase: TooltipPhase,
+    last_hidden: Option<Duration>,
+}
+
+impl TooltipState {
+    /// The source widget whose tooltip is currently visible, if any.
+    pub fn shown(&self) -> Option<Entity> {
+        match self.phase {
+            TooltipPhase::Shown | TooltipPhase::Closing { .. } => self.source,
+            _ => None,
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn update_tooltips(
+    time: Res<Time<Real>>,
+    settings: Res<TooltipSettings>,
+    mut state: ResMut<TooltipState>,
+    focus: Res<InputFocus>,
+    focus_visible: Res<InputFocusVisible>,
+    q_source: Query<(Entity, &TooltipSource, &Hovering)>,
+    q_tooltip_hover: Query<&Hovering, With<CoreTooltip>>,
+    mut q_tooltip: Query<&mut Visibility, With<CoreTooltip>>,
+) {
+    let now = time.elapsed();
+
+    // Work out which source, if any, currently wants its tooltip shown. Hovering takes priority
+    // over keyboard focus. Hovering over the tooltip itself keeps it open.
+    let hovered = q_source.iter().find(|(entity, source, hovering)| {
+        hovering.hovered
+            || (state.source == Some(*entity)
+                && q_tooltip_hover
+                    .get(source.tooltip)
+                    .is_ok_and(|hovering| hovering.hovered))
+    });
+    let engaged = match hovered {
+        Some((entity, _, _)) => Some((entity, false)),
+        None => focus
+            .0
+            .filter(|entity| focus_visible.0 && q_source.contains(*entity))
+            .map(|entity| (entity, true)),
+    };
+
+    let mut set_visible = |tooltip: Option<Entity>, visible: bool| {
+        if let Some(Ok(mut visibility)) = tooltip.map(|tooltip| q_tooltip.get_mut(tooltip)) {
+            visibility.set_if_neq(if visible {
+                Visibility::Inherited
+            } else {
+                Visibility::Hidden
+            });
+        }
+    };
+
+    // If the source was despawned, hide its tooltip and forget about it.
+    if let Some(source) = state.source {
+        if !q_source.contains(source) {
+            set_visible(state.tooltip, false);
+            state.source = None;
+            state.tooltip = None;
+            state.phase = TooltipPhase::Idle;
+        }
+    }
+
+    match engaged {
+        Some((source, from_focus)) if state.source == Some(source) => match state.phase {
+            TooltipPhase::Pending { since } if now - since >= settings.delay => {
+                state.phase = TooltipPhase::Shown;
+                set_visible(state.tooltip, true);
+            }
+            TooltipPhase::Closing { .. } => {
+                state.phase = TooltipPhase::Shown;
+            }
+            TooltipPhase::Idle => {
+                state.phase = TooltipPhase::Pending { since: now };
+                if from_focus {
+                    state.phase = TooltipPhase::Shown;
+                    set_visible(state.tooltip, true);
+                }
+            }
+            _ => {}
+        },
+        Some((source, from_focus)) => {
+            let was_shown = matches!(
+                state.phase,
+                TooltipPhase::Shown | TooltipPhase::Closing { .. }
+            );
+            set_visible(state.tooltip, false);
+            let recently_hidden = state
+                .last_hidden
+                .is_some_and(|hidden| now - hidden <= settings.skip_delay_window);
+            state.source = Some(source);
+            state.tooltip = q_source
+                .get(source)
+                .ok()
+                .map(|(_, source, _)| source.tooltip);
+            if from_focus || was_shown || recently_hidden {
+                state.phase = TooltipPhase::Shown;
+                set_visible(state.tooltip, true);
+            } else {
+                state.phase = TooltipPhase::Pending { since: now };
+            }
+        }
+        None => match (state.source, state.phase) {
+            (Some(_), TooltipPhase::Shown) => {
+                state.phase = TooltipPhase::Closing { since: now };
+            }
+            (Some(_), TooltipPhase::Closing { since }) if now - since >= settings.grace_period => {
+                set_visible(state.tooltip, false);
+                state.source = None;
+                state.tooltip = None;
+                state.phase = TooltipPhase::Idle;
+                state.last_hidden = Some(now);
+            }
+            (Some(_), TooltipPhase::Pending { .. }) => {
+                state.source = None;
+                state.tooltip = None;
+                state.phase = TooltipPhase::Idle;
+            }
+            _ => {}
+        },
+    }
+}
+
+pub struct CoreTooltipPlugin;
+
+impl Plugin for CoreTooltipPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<TooltipSettings>()
+            .init_resource::<TooltipState>()
+            .register_type::<CoreTooltip>()
+            .register_type::<TooltipSource>()
+            .register_type::<TooltipSettings>()
+            .add_systems(
+                Update,
+                update_tooltips.after(crate::hover::update_hover_states),
+            );
+    }
+}
@@ -1,49 +1,67 @@
 use bevy::{
-    picking::{hover::HoverMap, pointer::PointerId},
+    picking::{
+        hover::HoverMap,
+        pointer::{PointerId, PointerLocation},
+    },
     prelude::*,
+    render::camera::NormalizedRenderTarget,
     winit::cursor::CursorIcon,
 };
 
 /// A component that specifies the cursor icon to be used when the mouse is not hovering over
 /// any other entity. This is used to set the default cursor icon for the window.
-#[derive(Resource, Debug, Clone, Default)]
+#[derive(Resource, Debug, Clone, Default, Reflect)]
+#[reflect(Resource, Debug, Default)]
 pub struct DefaultCursorIcon(pub CursorIcon);
 
-/// System which updates the window cursor icon whenever the mouse hovers over an entity with
-/// a `CursorIcon` component. If no entity is hovered, the cursor icon is set to
-/// `CursorIcon::default()`.
+/// System which updates the cursor icon of each window whenever a pointer in that window hovers
+/// over an entity with a `CursorIcon` component. All pointers are considered, not just the
+/// mouse, although the mouse takes priority when several pointers are in the same window. If no
+/// entity is hovered, the cursor icon is set to the [`DefaultCursorIcon`].
 pub(crate) fn update_cursor(
     mut commands: Commands,
     hover_map: Option<Res<HoverMap>>,
     parent_query: Query<&ChildOf>,
     cursor_query: Query<&CursorIcon>,
+    q_pointers: Query<(&PointerId, &PointerLocation)>,
     mut q_windows: Query<(Entity, &mut Window, Option<&CursorIcon>)>,
     r_default_cursor: Res<DefaultCursorIcon>,
 ) {
-    let cursor = hover_map.and_then(|hover_map| match hover_map.get(&PointerId::Mouse) {
-        Some(hover_set) => hover_set.keys().find_map(|entity| {
-            cursor_query.get(*entity).ok().or_else(|| {
-                parent_query
-                    .iter_ancestors(*entity)
-                    .find_map(|e| cursor_query.get(e).ok())
-            })
-        }),
-        None => None,
-    });
+    let mut pointers: Vec<_> = q_pointers.iter().collect();
+    pointers.sort_by_key(|(pointer, _)| !pointer.is_mouse());
 
-    let mut windows_to_change: Vec<Entity> = Vec::new();
+    let mut windows_to_change: Vec<(Entity, Option<&CursorIcon>)> = Vec::new();
     for (entity, _window, prev_cursor) in q_windows.iter_mut() {
+        let cursor = hover_map.as_ref().and_then(|hover_map| {
+            pointers
+                .iter()
+                .filter(|(_, location)| {
+                    location.location().is_some_and(|location| {
+                        matches!(location.target, NormalizedRenderTarget::Window(window)
+                            if window.entity() == entity)
+                    })
+                })
+                .filter_map(|(pointer, _)| hover_map.get(*pointer))
+                .flat_map(|hover_set| hover_set.keys())
+                .find_map(|entity| {
+                    cursor_query.get(*entity).ok().or_else(|| {
+                        parent_query
+                            .iter_ancestors(*entity)
+                            .find_map(|e| cursor_query.get(e).ok())
+                    })
+                })
+        });
         match (cursor, prev_cursor) {
             (Some(cursor), Some(prev_cursor)) if cursor == prev_cursor => continue,
             (None, None) => continue,
             _ => {
-                windows_to_change.push(entity);
+                windows_to_change.push((entity, cursor));
             }
         }
     }
-    windows_to_change.iter().for_each(|entity| {
+    windows_to_change.iter().for_each(|(entity, cursor)| {
         if let Some(cursor) = cursor {
-            commands.entity(*entity).insert(cursor.clone());
+            commands.entity(*entity).insert((*cursor).clone());
         } else {
             commands.entity(*entity).insert(r_default_cursor.0.clone());
         }
@@ -57,6 +75,7 @@ impl Plugin for CursorIconPlugin {
         if app.world().get_resource::<DefaultCursorIcon>().is_none() {
             app.init_resource::<DefaultCursorIcon>();
         }
-        app.add_systems(Update, update_cursor);
+        app.register_type::<DefaultCursorIcon>()
+            .add_systems(Update, update_cursor);
     }
 }
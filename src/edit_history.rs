@@ -0,0 +1,210 @@
+use core::time::Duration;
+
+use bevy::prelude::*;
+
+use crate::{Callback, TextBuffer};
+
+/// Maximum time between keystrokes for them to be merged into the same undo step.
+const MERGE_TIME: Duration = Duration::from_secs(1);
+
+/// The kind of edit made to a text buffer. Consecutive edits of the same kind (other than
+/// [`TextEditKind::Other`]) made in quick succession are merged into a single undo step, so that
+/// undo removes a whole burst of typing rather than a single character.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextEditKind {
+    /// Typing characters.
+    Insert,
+    /// Deleting single characters with `Backspace` or `Delete`.
+    Delete,
+    /// Any other edit, such as pasting, cutting or deleting a word. These are never merged.
+    Other,
+}
+
+/// The state of a text buffer before an edit.
+#[derive(Debug, Clone)]
+struct TextSnapshot {
+    text: String,
+    anchor: usize,
+    cursor: usize,
+}
+
+impl TextSnapshot {
+    fn of(buffer: &TextBuffer) -> Self {
+        Self {
+            text: buffer.text().to_string(),
+            anchor: buffer.anchor(),
+            cursor: buffer.cursor(),
+        }
+    }
+
+    fn restore(self, buffer: &mut TextBuffer) {
+        buffer.set_text(self.text);
+        buffer.select_range(self.anchor..self.cursor);
+        buffer.uncommitted = true;
+    }
+}
+
+/// Undo and redo history for a text widget. [`CoreTextInput`] and [`CoreTextArea`] record every
+/// edit made by the user here, and undo them with `Ctrl+Z`; `Ctrl+Shift+Z` or `Ctrl+Y` redo.
+///
+/// The history is not aware of changes made to the [`TextBuffer`] by the app. An app which
+/// replaces the text should usually call [`EditHistory::clear`] at the same time, so that undo
+/// doesn't bring back the old text.
+///
+/// [`CoreTextInput`]: crate::CoreTextInput
+/// [`CoreTextArea`]: crate::CoreTextArea
+#[derive(Component, Debug, Clone, Reflect)]
+#[reflect(Component, Debug, Default)]
+pub struct EditHistory {
+    /// The maximum number of undo steps to keep. The oldest steps are discarded first.
+    pub max_steps: usize,
+    // The steps themselves are session state, so only the settings are reflected.
+    #[reflect(ignore)]
+    undo: Vec<TextSnapshot>,
+    #[reflect(ignore)]
+    redo: Vec<TextSnapshot>,
+    /// The kind and time of the most recent edit, if it can be merged with the next one.
+    #[reflect(ignore)]
+    last_edit: Option<(TextEditKind, Duration)>,
+}
+
+impl Default for EditHistory {
+    fn default() -> Self {
+        Self {
+            max_steps: 100,
+            undo: Vec::new(),
+            redo: Vec::new(),
+            last_edit: None,
+        }
+    }
+}
+
+impl EditHistory {
+    /// Record an edit. `before` is the state of the buffer before the edit was made, and `now`
+    /// is the current time, which is used to decide whether to merge the edit with the previous
+    /// one.
+    pub fn record(&mut self, before: &TextBuffer, kind: TextEditKind, now: Duration) {
+        self.redo.clear();
+        let merge = kind != TextEditKind::Other
+            && self
+                .last_edit
+                .is_some_and(|(last_kind, time)| last_kind == kind && now - time <= MERGE_TIME);
+        if !merge {
+            self.undo.push(TextSnapshot::of(before));
+            if self.undo.len() > self.max_steps {
+                self.undo.remove(0);
+            }
+        }
+        self.last_edit = Some((kind, now));
+    }
+
+    /// End the current undo step, so that the next edit isn't merged with the previous one. This
+    /// is called when the cursor is moved.
+    pub fn break_merge(&mut self) {
+        self.last_edit = None;
+    }
+
+    /// Returns true if there is an edit which can be undone.
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    /// Returns true if there is an undone edit which can be redone.
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    /// Undo the most recent edit to `buffer`. Returns false if there was nothing to undo.
+    pub fn undo(&mut self, buffer: &mut TextBuffer) -> bool {
+        let Some(snapshot) = self.undo.pop() else {
+            return false;
+        };
+        self.redo.push(TextSnapshot::of(buffer));
+        snapshot.restore(buffer);
+        self.last_edit = None;
+        true
+    }
+
+    /// Redo the most recently undone edit to `buffer`. Returns false if there was nothing to
+    /// redo.
+    pub fn redo(&mut self, buffer: &mut TextBuffer) -> bool {
+        let Some(snapshot) = self.redo.pop() else {
+            return false;
+        };
+        self.undo.push(TextSnapshot::of(buffer));
+        snapshot.restore(buffer);
+        self.last_edit = None;
+        true
+    }
+
+    /// Discard all undo and redo steps.
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+        self.last_edit = None;
+    }
+}
+
+/// The value of a widget, before or after a [`WidgetEdit`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WidgetValue {
+    /// The checked state of a checkbox.
+    Bool(bool),
+    /// The value of a slider.
+    Number(f32),
+    /// The selected radio button of a radio group, if any.
+    Selection(Option<Entity>),
+}
+
+/// A completed change to the value of a widget, reported so that it can be recorded on an
+/// app-level undo stack. Unlike [`ValueChange`], this is only reported once per user action:
+/// dragging a slider produces a single edit when the drag ends, containing the value from before
+/// the drag started.
+///
+/// Edits are only reported if the app has inserted the [`WidgetEditHook`] resource.
+///
+/// [`ValueChange`]: crate::ValueChange
+#[derive(Clone, Debug)]
+pub struct WidgetEdit {
+    /// The widget which was edited.
+    pub entity: Entity,
+    /// The value of the widget before the edit. Restoring this value undoes the edit.
+    pub before: WidgetValue,
+    /// The value of the widget after the edit.
+    pub after: WidgetValue,
+}
+
+impl Event for WidgetEdit {
+    type Traversal = &'static ChildOf;
+
+    const AUTO_PROPAGATE: bool = true;
+}
+
+/// Resource which opts in to reporting [`WidgetEdit`]s from sliders, checkboxes and radio
+/// groups. The `on_edit` callback is called for each edit; by default, a `WidgetEdit` event is
+/// emitted on the widget.
+///
+/// The widgets themselves don't undo anything: as with [`ValueChange`], it is up to the app to
+/// apply the `before` value of an edit when it is undone.
+///
+/// [`ValueChange`]: crate::ValueChange
+#[derive(Resource, Debug, Default)]
+pub struct WidgetEditHook {
+    pub on_edit: Callback<WidgetEdit>,
+}
+
+/// Report an edit to a widget, if the app has opted in to edit reporting.
+pub(crate) fn report_widget_edit(
+    commands: &mut Commands,
+    hook: Option<&WidgetEditHook>,
+    edit: WidgetEdit,
+) {
+    let Some(hook) = hook else {
+        return;
+    };
+    if edit.before == edit.after {
+        return;
+    }
+    let entity = edit.entity;
+    hook.on_edit.notify(commands, entity, edit, |edit| edit);
+}
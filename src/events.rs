@@ -11,6 +11,20 @@ impl<T: Send + Sync + 'static> Event for ValueChange<T> {
     const AUTO_PROPAGATE: bool = true;
 }
 
+/// An event that indicates that the user has finished editing a value, for example by pressing
+/// `Enter` in a text input, by moving focus elsewhere, or by releasing a slider at the end of a
+/// drag. Unlike [`ValueChange`], which may be emitted many times during an edit as a live preview,
+/// this is emitted once with the final value, which makes it the right place to record undo
+/// history or send the value over the network.
+#[derive(Clone, Debug)]
+pub struct ValueCommit<T>(pub T);
+
+impl<T: Send + Sync + 'static> Event for ValueCommit<T> {
+    type Traversal = &'static ChildOf;
+
+    const AUTO_PROPAGATE: bool = true;
+}
+
 /// An event which is emitted when a button is clicked. This is different from the
 /// [`Pointer<Click>`] event, because it's also emitted when the button is focused and the `Enter`
 /// or `Space` key is pressed.
@@ -22,3 +36,8 @@ impl Event for ButtonClicked {
 
     const AUTO_PROPAGATE: bool = true;
 }
+
+/// An event which is emitted when the user asks to close a dialog, for example by pressing the
+/// `Escape` key. The dialog is not closed automatically; it is up to the app to despawn it.
+#[derive(Clone, Debug, Event)]
+pub struct DialogCloseRequested;
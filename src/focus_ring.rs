@@ -0,0 +1,200 @@
+use bevy::{
+    input_focus::{InputFocus, InputFocusVisible},
+    prelude::*,
+    ui::{CalculatedClip, ComputedNodeTarget, UiSystem},
+};
+
+/// Resource which controls the default appearance of the focus ring drawn by
+/// [`FocusRingPlugin`]. Individual widgets can override these with a [`FocusRingStyle`].
+#[derive(Resource, Debug, Clone, Reflect)]
+#[reflect(Resource, Debug, Default)]
+pub struct FocusRingSettings {
+    /// The color of the ring.
+    pub color: Color,
+    /// The thickness of the ring, in logical pixels.
+    pub width: f32,
+    /// The distance from the edge of the widget to the outer edge of the ring, in logical
+    /// pixels. Negative values draw the ring outside of the widget.
+    pub inset: f32,
+}
+
+impl Default for FocusRingSettings {
+    fn default() -> Self {
+        Self {
+            color: Color::srgb(0.3, 0.5, 1.0),
+            width: 2.0,
+            inset: -3.0,
+        }
+    }
+}
+
+/// Component which overrides the [`FocusRingSettings`] for a single widget. Fields which are
+/// `None` use the global setting.
+#[derive(Component, Debug, Clone, Default, Reflect)]
+#[reflect(Component, Debug, Default)]
+pub struct FocusRingStyle {
+    /// Overrides [`FocusRingSettings::inset`].
+    pub inset: Option<f32>,
+    /// The corner radius of the ring, in logical pixels. By default the ring follows the
+    /// border radius of the widget.
+    pub radius: Option<f32>,
+    /// Overrides [`FocusRingSettings::color`].
+    pub color: Option<Color>,
+}
+
+/// Marker for the overlay node which displays the focus ring. This is a root UI node, drawn
+/// above all other UI, whose bounds are the clip rect of the focused widget; the ring itself is
+/// its only child.
+#[derive(Component, Debug, Clone, Copy, Reflect)]
+#[reflect(Component, Debug)]
+pub struct FocusRing;
+
+fn spawn_focus_ring(mut commands: Commands) {
+    commands.spawn((
+        Node {
+            position_type: PositionType::Absolute,
+            overflow: Overflow::clip(),
+            ..default()
+        },
+        GlobalZIndex(i32::MAX),
+        Pickable::IGNORE,
+        Visibility::Hidden,
+        FocusRing,
+        children![(
+            Node {
+                position_type: PositionType::Absolute,
+                ..default()
+            },
+            BorderColor::default(),
+            BorderRadius::default(),
+            Pickable::IGNORE,
+        )],
+    ));
+}
+
+/// System which moves the focus ring to the focused entity, and hides it when focus is not
+/// visible or the focused entity isn't a UI node.
+#[allow(clippy::type_complexity)]
+fn update_focus_ring(
+    mut commands: Commands,
+    focus: Res<InputFocus>,
+    focus_visible: Res<InputFocusVisible>,
+    settings: Res<FocusRingSettings>,
+    q_target: Query<(
+        &ComputedNode,
+        &GlobalTransform,
+        &InheritedVisibility,
+        Option<&ComputedNodeTarget>,
+        Option<&CalculatedClip>,
+        Option<&FocusRingStyle>,
+    )>,
+    mut q_overlay: Query<
+        (
+            Entity,
+            &mut Node,
+            &mut Visibility,
+            Option<&UiTargetCamera>,
+            &Children,
+        ),
+        With<FocusRing>,
+    >,
+    mut q_ring: Query<(&mut Node, &mut BorderColor, &mut BorderRadius), Without<FocusRing>>,
+) {
+    let Ok((overlay, mut overlay_node, mut visibility, target_camera, children)) =
+        q_overlay.single_mut()
+    else {
+        return;
+    };
+
+    let target = focus
+        .0
+        .filter(|_| focus_visible.0)
+        .and_then(|entity| q_target.get(entity).ok())
+        .filter(|(node, _, inherited, ..)| inherited.get() && !node.is_empty());
+    let Some((node, transform, _, node_target, clip, style)) = target else {
+        visibility.set_if_neq(Visibility::Hidden);
+        return;
+    };
+
+    // Layout values are in physical pixels, but `Val::Px` is in logical pixels.
+    let scale = node.inverse_scale_factor;
+    let inset = style
+        .and_then(|style| style.inset)
+        .unwrap_or(settings.inset);
+    let color = style
+        .and_then(|style| style.color)
+        .unwrap_or(settings.color);
+    let bounds = Rect::from_center_size(
+        transform.translation().truncate() * scale,
+        node.size() * scale,
+    )
+    .inflate(-inset);
+    let clip = clip.map_or(bounds, |clip| {
+        Rect::from_corners(clip.clip.min * scale, clip.clip.max * scale)
+    });
+    if clip.intersect(bounds).is_empty() {
+        visibility.set_if_neq(Visibility::Hidden);
+        return;
+    }
+
+    visibility.set_if_neq(Visibility::Inherited);
+    overlay_node.left = Val::Px(clip.min.x);
+    overlay_node.top = Val::Px(clip.min.y);
+    overlay_node.width = Val::Px(clip.width());
+    overlay_node.height = Val::Px(clip.height());
+
+    let camera = node_target.and_then(ComputedNodeTarget::camera);
+    if camera != target_camera.map(UiTargetCamera::entity) {
+        match camera {
+            Some(camera) => commands.entity(overlay).insert(UiTargetCamera(camera)),
+            None => commands.entity(overlay).remove::<UiTargetCamera>(),
+        };
+    }
+
+    let Some(Ok((mut ring_node, mut border_color, mut border_radius))) =
+        children.first().map(|ring| q_ring.get_mut(*ring))
+    else {
+        return;
+    };
+    ring_node.left = Val::Px(bounds.min.x - clip.min.x);
+    ring_node.top = Val::Px(bounds.min.y - clip.min.y);
+    ring_node.width = Val::Px(bounds.width());
+    ring_node.height = Val::Px(bounds.height());
+    ring_node.border = UiRect::all(Val::Px(settings.width));
+    border_color.set_if_neq(BorderColor(color));
+
+    // Unless overridden, round the ring so that it stays parallel to the widget's corners.
+    let radius = |corner: f32| match style.and_then(|style| style.radius) {
+        Some(radius) => Val::Px(radius),
+        None if corner > 0.0 => Val::Px((corner * scale - inset).max(0.0)),
+        None => Val::ZERO,
+    };
+    let corners = node.border_radius();
+    border_radius.set_if_neq(BorderRadius {
+        top_left: radius(corners.top_left),
+        top_right: radius(corners.top_right),
+        bottom_left: radius(corners.bottom_left),
+        bottom_right: radius(corners.bottom_right),
+    });
+}
+
+/// Plugin which draws a ring around the focused entity when [`InputFocusVisible`] is true, such
+/// as after keyboard navigation. The ring is a single overlay node, rather than an `Outline` on
+/// each widget, so that widgets don't need to reserve room for it; it is clipped by the scroll
+/// containers which clip the focused widget. The appearance is controlled by the
+/// [`FocusRingSettings`] resource and the [`FocusRingStyle`] component.
+///
+/// Unlike the widget plugins, this is not part of [`CoreWidgetsPlugin`](crate::CoreWidgetsPlugin),
+/// since it draws something; apps which draw their own focus indicators shouldn't add it.
+pub struct FocusRingPlugin;
+
+impl Plugin for FocusRingPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<FocusRingSettings>()
+            .register_type::<FocusRingSettings>()
+            .register_type::<FocusRingStyle>()
+            .register_type::<FocusRing>()
+            .add_systems(Startup, spawn_focus_ring)
+            .add_systems(PostUpdate, update_focus_ring.before(UiSystem::Prepare));
+    }
+}
@@ -1,13 +1,28 @@
 use bevy::{
     picking::{hover::HoverMap, pointer::PointerId},
+    platform::collections::HashMap,
     prelude::*,
 };
 // use thorium_ui_core::Signal;
 
-/// Component which indicates that the entity is interested in knowing when the mouse is hovering
-/// over it or any of its children.
-#[derive(Debug, Clone, Copy, Component, Default)]
-pub struct Hovering(pub bool);
+/// Component which indicates that the entity is interested in knowing when a pointer is hovering
+/// over it or any of its children. All pointers are tracked, including touches and custom
+/// pointers, so that an entity can tell which of them are hovering over it.
+#[derive(Debug, Clone, Component, Default, PartialEq, Reflect)]
+#[reflect(Component, Debug, Default, PartialEq)]
+pub struct Hovering {
+    /// True if any pointer is hovering over the entity.
+    pub hovered: bool,
+    /// The pointers which are hovering over the entity, in no particular order.
+    pub pointers: Vec<PointerId>,
+}
+
+impl Hovering {
+    /// Returns true if the given pointer is hovering over the entity.
+    pub fn is_hovered_by(&self, pointer: PointerId) -> bool {
+        self.pointers.contains(&pointer)
+    }
+}
 
 // Note: previously this was implemented as a Reaction, however it was reacting every frame
 // because HoverMap is mutated every frame regardless of whether or not it changed.
@@ -17,16 +32,30 @@ pub(crate) fn update_hover_states(
     parent_query: Query<&ChildOf>,
 ) {
     let Some(hover_map) = hover_map else { return };
-    let hover_set = hover_map.get(&PointerId::Mouse);
+
+    // The pointers hovering over each entity, either directly or over one of its descendants.
+    let mut hovered: HashMap<Entity, Vec<PointerId>> = HashMap::new();
+    for (pointer, hover_set) in hover_map.iter() {
+        for &entity in hover_set.keys() {
+            for entity in core::iter::once(entity).chain(parent_query.iter_ancestors(entity)) {
+                let pointers = hovered.entry(entity).or_default();
+                if !pointers.contains(pointer) {
+                    pointers.push(*pointer);
+                }
+            }
+        }
+    }
+
     for (entity, mut hoverable) in hovers.iter_mut() {
-        let is_hovering = match hover_set {
-            Some(map) => map.iter().any(|(ha, _)| {
-                *ha == entity || parent_query.iter_ancestors(*ha).any(|e| e == entity)
-            }),
-            None => false,
-        };
-        if hoverable.0 != is_hovering {
-            hoverable.0 = is_hovering;
+        let pointers = hovered.remove(&entity).unwrap_or_default();
+        // The hover map is unordered, so compare the pointers as sets.
+        if hoverable.pointers.len() != pointers.len()
+            || !pointers
+                .iter()
+                .all(|pointer| hoverable.is_hovered_by(*pointer))
+        {
+            hoverable.hovered = !pointers.is_empty();
+            hoverable.pointers = pointers;
         }
     }
 }
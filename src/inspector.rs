@@ -0,0 +1,354 @@
+use core::any::TypeId;
+
+use accesskit::Role;
+use bevy::{
+    a11y::AccessibilityNode,
+    input_focus::tab_navigation::TabIndex,
+    prelude::*,
+    reflect::{attributes::CustomAttributes, TypeInfo, TypeRegistration, VariantInfo},
+};
+
+use crate::{
+    BindingOption, BindingTarget, Callback, CoreButton, CoreCheckbox, CoreRadio, CoreRadioGroup,
+    CoreSlider, CoreSpinBox, CoreTextInput, WidgetBinding,
+};
+
+/// Reflection attribute which gives the range of a numeric field in an inspector. Fields with a
+/// range are edited with a [`CoreSlider`]; other numeric fields are edited with a
+/// [`CoreSpinBox`].
+///
+/// ```ignore
+/// #[derive(Component, Reflect)]
+/// #[reflect(Component)]
+/// struct Light {
+///     #[reflect(@InspectorRange { min: 0.0, max: 1.0 })]
+///     intensity: f32,
+/// }
+/// ```
+#[derive(Reflect, Debug, Clone, Copy, PartialEq)]
+pub struct InspectorRange {
+    pub min: f32,
+    pub max: f32,
+}
+
+/// Reflection attribute which gives the increment of a numeric field in an inspector, used when
+/// stepping the value with the keyboard. The default is 1.
+#[derive(Reflect, Debug, Clone, Copy, PartialEq)]
+pub struct InspectorStep(pub f32);
+
+/// Marker component for each row of an inspector, containing the label and widget for a field.
+#[derive(Component, Debug, Clone, Reflect)]
+#[reflect(Component, Debug)]
+pub struct InspectorField {
+    /// The reflection path of the field.
+    pub path: String,
+}
+
+/// Marker component for an inspector section, which contains the fields of a nested struct.
+/// The first child of the section is a [`CoreButton`] which expands and collapses it, and the
+/// second contains the fields.
+#[derive(Component, Debug, Clone, Copy, Reflect)]
+#[reflect(Component, Debug)]
+pub struct InspectorSection;
+
+/// The kind of number a numeric field holds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum NumberKind {
+    Float,
+    Signed,
+    Unsigned,
+}
+
+impl NumberKind {
+    fn of(type_id: TypeId) -> Option<Self> {
+        if [TypeId::of::<f32>(), TypeId::of::<f64>()].contains(&type_id) {
+            Some(Self::Float)
+        } else if [
+            TypeId::of::<i8>(),
+            TypeId::of::<i16>(),
+            TypeId::of::<i32>(),
+            TypeId::of::<i64>(),
+            TypeId::of::<isize>(),
+        ]
+        .contains(&type_id)
+        {
+            Some(Self::Signed)
+        } else if [
+            TypeId::of::<u8>(),
+            TypeId::of::<u16>(),
+            TypeId::of::<u32>(),
+            TypeId::of::<u64>(),
+            TypeId::of::<usize>(),
+        ]
+        .contains(&type_id)
+        {
+            Some(Self::Unsigned)
+        } else {
+            None
+        }
+    }
+}
+
+/// Spawn a tree of core widgets which edit the fields of a reflected component or resource, and
+/// return the root entity. If `entity` is `Some`, the component on that entity is edited;
+/// otherwise the resource is edited. The type must be registered with [`ReflectComponent`] or
+/// [`ReflectResource`] type data, as for [`WidgetBinding`].
+///
+/// Each supported field gets an [`InspectorField`] row, containing a [`Text`] label and a widget
+/// bound to the field with a [`WidgetBinding`], so that edits are applied live:
+///
+/// * `bool` fields are edited with a [`CoreCheckbox`].
+/// * Numeric fields are edited with a [`CoreSlider`] if they have an [`InspectorRange`]
+///   attribute, or a [`CoreSpinBox`] if they don't. The increment is taken from the
+///   [`InspectorStep`] attribute.
+/// * `String` fields are edited with a [`CoreTextInput`].
+/// * Enums with only unit variants are edited with a [`CoreRadioGroup`], with a [`CoreRadio`] for
+///   each variant.
+/// * Nested structs are placed in a collapsible [`InspectorSection`].
+///
+/// Fields of other types are skipped. Like all core widgets, the spawned widgets have no visual
+/// styling: the app is expected to style them, for example by observing when the widget
+/// components are added.
+pub fn spawn_inspector(
+    commands: &mut Commands,
+    registration: &TypeRegistration,
+    entity: Option<Entity>,
+) -> Entity {
+    let type_path = registration.type_info().type_path().to_string();
+    let target = match entity {
+        Some(entity) => BindingTarget::Component(entity, type_path),
+        None => BindingTarget::Resource(type_path),
+    };
+    let root = commands
+        .spawn(Node {
+            flex_direction: FlexDirection::Column,
+            ..default()
+        })
+        .id();
+    spawn_fields(commands, root, registration.type_info(), &target, "");
+    root
+}
+
+/// Spawn rows for the fields of a struct.
+fn spawn_fields(
+    commands: &mut Commands,
+    parent: Entity,
+    info: &TypeInfo,
+    target: &BindingTarget,
+    prefix: &str,
+) {
+    match info {
+        TypeInfo::Struct(info) => {
+            for field in info.iter() {
+                spawn_field(
+                    commands,
+                    parent,
+                    field.name(),
+                    field.type_info(),
+                    field.custom_attributes(),
+                    WidgetBinding {
+                        target: target.clone(),
+                        path: format!("{prefix}.{}", field.name()),
+                    },
+                );
+            }
+        }
+        TypeInfo::TupleStruct(info) => {
+            for field in info.iter() {
+                spawn_field(
+                    commands,
+                    parent,
+                    &field.index().to_string(),
+                    field.type_info(),
+                    field.custom_attributes(),
+                    WidgetBinding {
+                        target: target.clone(),
+                        path: format!("{prefix}.{}", field.index()),
+                    },
+                );
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Spawn the row for a single field, if its type is supported.
+fn spawn_field(
+    commands: &mut Commands,
+    parent: Entity,
+    label: &str,
+    info: Option<&TypeInfo>,
+    attributes: &CustomAttributes,
+    binding: WidgetBinding,
+) {
+    let Some(info) = info else {
+        return;
+    };
+    let type_id = info.type_id();
+    let widget = if type_id == TypeId::of::<bool>() {
+        commands
+            .spawn((
+                Node::default(),
+                CoreCheckbox::default(),
+                TabIndex(0),
+                binding.clone(),
+            ))
+            .id()
+    } else if let Some(kind) = NumberKind::of(type_id) {
+        let increment = attributes.get::<InspectorStep>().map_or(1.0, |step| step.0);
+        match attributes.get::<InspectorRange>() {
+            Some(range) => commands
+                .spawn((
+                    Node::default(),
+                    CoreSlider {
+                        value: range.min,
+                        min: range.min,
+                        max: range.max,
+                        increment,
+                        ..default()
+                    },
+                    TabIndex(0),
+                    binding.clone(),
+                ))
+                .id(),
+            None => commands
+                .spawn((
+                    Node::default(),
+                    CoreSpinBox {
+                        min: if kind == NumberKind::Unsigned {
+                            0.0
+                        } else {
+                            f32::MIN
+                        },
+                        increment,
+                        precision: (kind != NumberKind::Float).then_some(0),
+                        ..default()
+                    },
+                    TabIndex(0),
+                    binding.clone(),
+                    children![Text::default()],
+                ))
+                .id(),
+        }
+    } else if type_id == TypeId::of::<String>() {
+        commands
+            .spawn((
+                Node::default(),
+                CoreTextInput::default(),
+                TabIndex(0),
+                binding.clone(),
+                children![Text::default()],
+            ))
+            .id()
+    } else {
+        match info {
+            TypeInfo::Enum(info)
+                if info
+                    .iter()
+                    .all(|variant| matches!(variant, VariantInfo::Unit(_))) =>
+            {
+                let group = commands
+                    .spawn((
+                        Node::default(),
+                        CoreRadioGroup::default(),
+                        TabIndex(0),
+                        binding.clone(),
+                    ))
+                    .id();
+                for name in info.variant_names() {
+                    commands.spawn((
+                        Node::default(),
+                        CoreRadio,
+                        BindingOption(name.to_string()),
+                        Text::new(*name),
+                        ChildOf(group),
+                    ));
+                }
+                group
+            }
+            TypeInfo::Struct(_) | TypeInfo::TupleStruct(_) => {
+                spawn_section(commands, parent, label, info, binding);
+                return;
+            }
+            _ => return,
+        }
+    };
+
+    commands
+        .entity(widget)
+        .entry::<AccessibilityNode>()
+        .and_modify({
+            let label = label.to_string();
+            move |mut node| node.set_label(label)
+        });
+    let label = commands.spawn(Text::new(label)).id();
+    commands
+        .spawn((
+            Node {
+                flex_direction: FlexDirection::Row,
+                align_items: AlignItems::Center,
+                column_gap: Val::Px(8.0),
+                ..default()
+            },
+            InspectorField { path: binding.path },
+            ChildOf(parent),
+        ))
+        .add_children(&[label, widget]);
+}
+
+/// Spawn a collapsible section containing the fields of a nested struct.
+fn spawn_section(
+    commands: &mut Commands,
+    parent: Entity,
+    label: &str,
+    info: &TypeInfo,
+    binding: WidgetBinding,
+) {
+    let content = commands
+        .spawn(Node {
+            flex_direction: FlexDirection::Column,
+            padding: UiRect::left(Val::Px(12.0)),
+            ..default()
+        })
+        .id();
+    spawn_fields(commands, content, info, &binding.target, &binding.path);
+
+    let mut header_node = accesskit::Node::new(Role::Button);
+    header_node.set_expanded(true);
+    let header = commands
+        .spawn((
+            CoreButton {
+                on_click: Callback::closure(move |world, header, ()| {
+                    let Some(mut node) = world.get_mut::<Node>(content) else {
+                        return;
+                    };
+                    let expanded = node.display == Display::None;
+                    node.display = if expanded {
+                        Display::Flex
+                    } else {
+                        Display::None
+                    };
+                    if let Some(mut accessibility) = world.get_mut::<AccessibilityNode>(header) {
+                        accessibility.set_expanded(expanded);
+                    }
+                }),
+            },
+            AccessibilityNode(header_node),
+            Node::default(),
+            TabIndex(0),
+            Text::new(label),
+        ))
+        .id();
+
+    commands
+        .spawn((
+            Node {
+                flex_direction: FlexDirection::Column,
+                ..default()
+            },
+            InspectorSection,
+            InspectorField { path: binding.path },
+            ChildOf(parent),
+        ))
+        .add_children(&[header, content]);
+}
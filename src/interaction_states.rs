@@ -1,21 +1,124 @@
 use bevy::{
-    a11y::AccessibilityNode,
+    a11y::{AccessibilityNode, AccessibilitySystem},
     ecs::{component::HookContext, world::DeferredWorld},
-    prelude::Component,
+    input_focus::{InputFocus, InputFocusSet, InputFocusVisible},
+    picking::{hover::HoverMap, pointer::PointerId, PickSet},
+    platform::collections::HashSet,
+    prelude::*,
 };
 
+use crate::{core_scrollbar::ScrollbarDragState, SliderDragState};
+
 /// A marker component to indicate that a widget is disabled and should be "grayed out".
 /// This is used to prevent user interaction with the widget. It should not, however, prevent
 /// the widget from being updated or rendered, or from acquiring keyboard focus.
 ///
-/// For apps which support a11y: if a widget (such as a slider) contains multiple entities,
-/// the `InteractionDisabled` component should be added to the root entity of the widget - the
-/// same entity that contains the `AccessibilityNode` component. This will ensure that
-/// the a11y tree is updated correctly.
-#[derive(Component, Debug, Clone, Copy)]
-#[component(on_add = on_add_disabled, on_remove = on_remove_disabled)]
+/// The marker also disables all of the descendants of the entity, so that a whole form section
+/// can be disabled at once. Widgets check for [`EffectivelyDisabled`], which is computed from
+/// this marker, rather than checking for this marker directly.
+#[derive(Component, Debug, Clone, Copy, Reflect)]
+#[reflect(Component, Debug)]
 pub struct InteractionDisabled;
 
+/// A marker component which indicates that a widget is disabled, either because it has the
+/// [`InteractionDisabled`] marker, or because one of its ancestors does. This is maintained
+/// automatically at the end of each frame, and shouldn't be inserted or removed by the app;
+/// style systems should use it to decide whether to draw a widget as disabled.
+///
+/// For apps which support a11y, this sets the "disabled" state of the entity's
+/// `AccessibilityNode`.
+#[derive(Component, Debug, Clone, Copy, Reflect)]
+#[reflect(Component, Debug)]
+#[component(on_add = on_add_disabled, on_remove = on_remove_disabled)]
+pub struct EffectivelyDisabled;
+
+/// Add or remove [`EffectivelyDisabled`] on entities whose disabled state, inherited from their
+/// ancestors, may have changed. Only the subtrees under entities which have been disabled,
+/// enabled or moved are walked; each starts from the current state of its parent.
+#[allow(clippy::type_complexity)]
+fn update_effectively_disabled(
+    q_changed: Query<Entity, Or<(Added<InteractionDisabled>, Changed<ChildOf>)>>,
+    mut removed_disabled: RemovedComponents<InteractionDisabled>,
+    mut removed_parents: RemovedComponents<ChildOf>,
+    q_parents: Query<&ChildOf>,
+    q_state: Query<(Has<InteractionDisabled>, Has<EffectivelyDisabled>)>,
+    q_children: Query<&Children>,
+    mut commands: Commands,
+) {
+    let changed: HashSet<Entity> = q_changed
+        .iter()
+        .chain(removed_disabled.read())
+        .chain(removed_parents.read())
+        .collect();
+
+    // Subtrees inside another changed subtree are covered by the outer walk.
+    let mut stack: Vec<(Entity, bool)> = changed
+        .iter()
+        .filter(|entity| {
+            !q_parents
+                .iter_ancestors(**entity)
+                .any(|ancestor| changed.contains(&ancestor))
+        })
+        .map(|&entity| {
+            let inherited = q_parents
+                .get(entity)
+                .and_then(|child_of| q_state.get(child_of.parent()))
+                .is_ok_and(|(_, parent_disabled)| parent_disabled);
+            (entity, inherited)
+        })
+        .collect();
+    while let Some((entity, inherited)) = stack.pop() {
+        let Ok((is_disabled, was_disabled)) = q_state.get(entity) else {
+            continue;
+        };
+        let disabled = inherited || is_disabled;
+        if disabled != was_disabled {
+            if disabled {
+                commands.entity(entity).insert(EffectivelyDisabled);
+            } else {
+                commands.entity(entity).remove::<EffectivelyDisabled>();
+            }
+        }
+        if let Ok(children) = q_children.get(entity) {
+            stack.extend(children.iter().map(|child| (child, disabled)));
+        }
+    }
+}
+
+/// Plugin which maintains the state components shared by the widgets, such as
+/// [`EffectivelyDisabled`] and [`InteractionState`]. Every widget plugin which depends on them adds this plugin, so that
+/// widgets work without [`CoreWidgetsPlugin`](crate::CoreWidgetsPlugin).
+pub(crate) struct InteractionStatesPlugin;
+
+impl InteractionStatesPlugin {
+    /// Add the plugin to `app`, unless another widget plugin has already added it.
+    pub(crate) fn add_once(app: &mut App) {
+        if !app.is_plugin_added::<Self>() {
+            app.add_plugins(Self);
+        }
+    }
+}
+
+impl Plugin for InteractionStatesPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<InputFocus>()
+            .init_resource::<InputFocusVisible>()
+            .register_type::<InteractionDisabled>()
+            .register_type::<EffectivelyDisabled>()
+            .register_type::<InteractionState>()
+            .add_systems(
+                PreUpdate,
+                update_interaction_states
+                    .after(PickSet::Last)
+                    .after(InputFocusSet::Dispatch),
+            )
+            .add_systems(
+                PostUpdate,
+                update_effectively_disabled.before(AccessibilitySystem::Update),
+            );
+    }
+}
+
 // Hook to set the a11y "disabled" state when the widget is disabled.
 fn on_add_disabled(mut world: DeferredWorld, context: HookContext) {
     let mut entt = world.entity_mut(context.entity);
@@ -32,13 +135,87 @@ fn on_remove_disabled(mut world: DeferredWorld, context: HookContext) {
     }
 }
 
+/// A marker component to indicate that a widget's value can be read but not changed, for
+/// example because the user doesn't have permission to edit it. Unlike a disabled widget, a
+/// read-only widget is not "grayed out": it can still be focused, and the text of a read-only
+/// text input can still be selected and copied. However, the widget rejects any user input which
+/// would change its value:
+///
+/// * A [`CoreCheckbox`] can't be toggled.
+/// * A [`CoreRadioGroup`] can't change its selection.
+/// * A [`CoreSlider`] can't be dragged or stepped with the keyboard.
+/// * A [`CoreTextInput`] or [`CoreTextArea`] can't be edited. This includes spin boxes and scrub
+///   fields, which also can't be stepped or scrubbed.
+///
+/// For apps which support a11y, this sets the "read only" state of the entity's
+/// `AccessibilityNode`.
+///
+/// [`CoreCheckbox`]: crate::CoreCheckbox
+/// [`CoreRadioGroup`]: crate::CoreRadioGroup
+/// [`CoreSlider`]: crate::CoreSlider
+/// [`CoreTextInput`]: crate::CoreTextInput
+/// [`CoreTextArea`]: crate::CoreTextArea
+#[derive(Component, Debug, Clone, Copy, Reflect)]
+#[reflect(Component, Debug)]
+#[component(on_add = on_add_read_only, on_remove = on_remove_read_only)]
+pub struct InteractionReadOnly;
+
+// Hook to set the a11y "read only" state when the widget is made read-only.
+fn on_add_read_only(mut world: DeferredWorld, context: HookContext) {
+    let mut entt = world.entity_mut(context.entity);
+    if let Some(mut accessibility) = entt.get_mut::<AccessibilityNode>() {
+        accessibility.set_read_only();
+    }
+}
+
+// Hook to remove the a11y "read only" state when the widget is made editable.
+fn on_remove_read_only(mut world: DeferredWorld, context: HookContext) {
+    let mut entt = world.entity_mut(context.entity);
+    if let Some(mut accessibility) = entt.get_mut::<AccessibilityNode>() {
+        accessibility.clear_read_only();
+    }
+}
+
 /// Component that indicates whether a button is currently pressed. This will be true while
 /// a drag action is in progress.
-#[derive(Component, Default, Debug)]
-pub struct ButtonPressed(pub bool);
+///
+/// Presses are tracked per pointer, so that a button stays pressed until every pointer which
+/// pressed it has been released, and so that multiple touches on different buttons don't
+/// interfere with each other.
+#[derive(Component, Default, Debug, Clone, PartialEq, Reflect)]
+#[reflect(Component, Default, Debug, PartialEq)]
+pub struct ButtonPressed {
+    /// True if any pointer is pressing the button.
+    pub pressed: bool,
+    /// The pointers which are pressing the button.
+    pub pointers: Vec<PointerId>,
+}
+
+impl ButtonPressed {
+    /// Record that the given pointer has pressed the button.
+    pub fn press(&mut self, pointer: PointerId) {
+        if !self.pointers.contains(&pointer) {
+            self.pointers.push(pointer);
+        }
+        self.pressed = true;
+    }
+
+    /// Record that the given pointer has been released or cancelled. The button remains pressed
+    /// if other pointers are still pressing it.
+    pub fn release(&mut self, pointer: PointerId) {
+        self.pointers.retain(|p| *p != pointer);
+        self.pressed = !self.pointers.is_empty();
+    }
+
+    /// Returns true if the given pointer is pressing the button.
+    pub fn is_pressed_by(&self, pointer: PointerId) -> bool {
+        self.pointers.contains(&pointer)
+    }
+}
 
 /// Component that indicates whether a checkbox or radio button is in a checked state.
-#[derive(Component, Default, Debug)]
+#[derive(Component, Default, Debug, Reflect)]
+#[reflect(Component, Default, Debug)]
 #[component(immutable, on_add = on_add_checked, on_replace = on_add_checked)]
 pub struct Checked(pub bool);
 
@@ -52,3 +229,97 @@ fn on_add_checked(mut world: DeferredWorld, context: HookContext) {
         false => accesskit::Toggled::False,
     });
 }
+
+/// Marker component which makes a widget manage its own state. By default, widgets are
+/// "controlled": they report changes via their callbacks, but don't change their own state, so
+/// that the app can validate or transform the new value before applying it. An uncontrolled
+/// widget applies the change itself, as well as calling the callback:
+///
+/// * A [`CoreCheckbox`] toggles its [`Checked`] state.
+/// * A [`CoreRadioGroup`] checks the selected [`CoreRadio`] and unchecks the others.
+/// * A [`CoreSlider`] or [`CoreSpinBox`] stores the new value, clamped to its range.
+///
+/// [`CoreCheckbox`]: crate::CoreCheckbox
+/// [`CoreRadioGroup`]: crate::CoreRadioGroup
+/// [`CoreRadio`]: crate::CoreRadio
+/// [`CoreSlider`]: crate::CoreSlider
+/// [`CoreSpinBox`]: crate::CoreSpinBox
+#[derive(Component, Default, Debug, Clone, Copy, Reflect)]
+#[reflect(Component, Default, Debug)]
+pub struct Uncontrolled;
+
+/// Component which summarizes the interaction state of a widget, so that a style system can
+/// react to any change with a single `Changed<InteractionState>` filter, rather than querying
+/// several components and resources. The interactive core widgets have this component: buttons,
+/// checkboxes, radio buttons and radio groups, sliders, scrollbars, spin boxes and their buttons,
+/// and text inputs (including text areas and scrub fields). It can be added to any other entity,
+/// such as a dialog or tooltip, to track whether it is hovered or focused.
+///
+/// The state is computed during `PreUpdate`, after input has been handled, so systems in
+/// `Update` see the state for the current frame. It should not be modified by the app.
+#[derive(Component, Debug, Default, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Component, Debug, Default, PartialEq)]
+pub struct InteractionState {
+    /// A pointer is over the entity or one of its descendants.
+    pub hovered: bool,
+    /// The widget is being pressed, as given by [`ButtonPressed`], or a slider or scrollbar is
+    /// being dragged.
+    pub pressed: bool,
+    /// The entity has keyboard focus.
+    pub focused: bool,
+    /// The entity has keyboard focus, and the focus ring should be shown.
+    pub focus_visible: bool,
+    /// The widget is disabled, as given by [`EffectivelyDisabled`].
+    pub disabled: bool,
+    /// The widget is checked, as given by [`Checked`].
+    pub checked: bool,
+}
+
+#[allow(clippy::type_complexity)]
+fn update_interaction_states(
+    hover_map: Option<Res<HoverMap>>,
+    mut focus: ResMut<InputFocus>,
+    focus_visible: Res<InputFocusVisible>,
+    q_entities: Query<()>,
+    q_parents: Query<&ChildOf>,
+    mut q_state: Query<(
+        Entity,
+        &mut InteractionState,
+        Option<&ButtonPressed>,
+        Option<&SliderDragState>,
+        Option<&ScrollbarDragState>,
+        Option<&Checked>,
+        Has<EffectivelyDisabled>,
+    )>,
+) {
+    // Don't leave focus on an entity which has been despawned.
+    if focus.0.is_some_and(|entity| !q_entities.contains(entity)) {
+        focus.0 = None;
+    }
+
+    // Entities under any pointer, and their ancestors.
+    let mut hovered = HashSet::new();
+    if let Some(hover_map) = hover_map {
+        for &entity in hover_map.values().flat_map(|hits| hits.keys()) {
+            if hovered.insert(entity) {
+                hovered.extend(q_parents.iter_ancestors(entity));
+            }
+        }
+    }
+
+    for (entity, mut state, pressed, slider_drag, scrollbar_drag, checked, disabled) in
+        q_state.iter_mut()
+    {
+        let focused = focus.0 == Some(entity);
+        state.set_if_neq(InteractionState {
+            hovered: hovered.contains(&entity),
+            pressed: pressed.is_some_and(|pressed| pressed.pressed)
+                || slider_drag.is_some_and(|drag| drag.dragging)
+                || scrollbar_drag.is_some_and(|drag| drag.dragging),
+            focused,
+            focus_visible: focused && focus_visible.0,
+            disabled,
+            checked: checked.is_some_and(|checked| checked.0),
+        });
+    }
+}
@@ -1,41 +1,107 @@
+extern crate alloc;
+
 use bevy::app::{App, Plugin, Update};
+mod binding;
+mod callback;
+mod clipboard;
 mod core_barrier;
 mod core_button;
 mod core_checkbox;
+mod core_dialog;
+mod core_popover;
 mod core_radio;
 mod core_radio_group;
 mod core_scrollbar;
+mod core_scrub_field;
 mod core_slider;
+mod core_spin_box;
+mod core_text_area;
+mod core_text_input;
+mod core_tooltip;
 mod cursor;
+mod edit_history;
 mod events;
+mod focus_ring;
 pub mod hover;
+mod inspector;
 mod interaction_states;
+mod text_buffer;
+pub mod text_layout;
+mod widget_template;
 
+pub use binding::{BindingOption, BindingTarget, WidgetBinding, WidgetBindingPlugin};
+pub use callback::{Callback, CallbackFn, CallbackRegistry, NamedCallbacks, WidgetEvent};
+#[cfg(feature = "system_clipboard")]
+pub use clipboard::SystemClipboard;
+pub use clipboard::{Clipboard, ClipboardBackend, MemoryClipboard};
 pub use core_barrier::{CoreBarrier, CoreBarrierPlugin};
 pub use core_button::{CoreButton, CoreButtonPlugin};
 pub use core_checkbox::{CoreCheckbox, CoreCheckboxPlugin};
+pub use core_dialog::{CoreDialog, CoreDialogPlugin, DialogStack};
+pub use core_popover::{
+    CorePopover, CorePopoverPlugin, PopoverAlign, PopoverPlacement, PopoverSide,
+};
 pub use core_radio::{CoreRadio, CoreRadioPlugin};
 pub use core_radio_group::{CoreRadioGroup, CoreRadioGroupPlugin};
 pub use core_scrollbar::{CoreScrollbar, CoreScrollbarPlugin, CoreScrollbarThumb, Orientation};
+pub use core_scrub_field::{CoreScrubField, CoreScrubFieldPlugin};
 pub use core_slider::{CoreSlider, CoreSliderPlugin, SliderDragState};
+pub use core_spin_box::{CoreSpinBox, CoreSpinBoxButton, CoreSpinBoxPlugin, SpinDirection};
+pub use core_text_area::{CoreTextArea, CoreTextAreaPlugin};
+pub use core_text_input::{CoreTextInput, CoreTextInputPlugin};
+pub use core_tooltip::{
+    CoreTooltip, CoreTooltipPlugin, TooltipSettings, TooltipSource, TooltipState,
+};
 pub use cursor::CursorIconPlugin;
-pub use events::{ButtonClicked, ValueChange};
-pub use interaction_states::{ButtonPressed, Checked, InteractionDisabled};
+pub use edit_history::{EditHistory, TextEditKind, WidgetEdit, WidgetEditHook, WidgetValue};
+pub use events::{ButtonClicked, DialogCloseRequested, ValueChange, ValueCommit};
+pub use focus_ring::{FocusRing, FocusRingPlugin, FocusRingSettings, FocusRingStyle};
+pub use inspector::{
+    spawn_inspector, InspectorField, InspectorRange, InspectorSection, InspectorStep,
+};
+pub use interaction_states::{
+    ButtonPressed, Checked, EffectivelyDisabled, InteractionDisabled, InteractionReadOnly,
+    InteractionState, Uncontrolled,
+};
+pub use text_buffer::TextBuffer;
+pub use widget_template::{
+    WidgetTemplate, WidgetTemplateLoader, WidgetTemplateLoaderError, WidgetTemplatePlugin,
+    WidgetTemplateRoot,
+};
 
 pub struct CoreWidgetsPlugin;
 
 impl Plugin for CoreWidgetsPlugin {
     fn build(&self, app: &mut App) {
         app.add_plugins((
-            CoreBarrierPlugin,
-            CoreButtonPlugin,
-            CoreCheckboxPlugin,
-            CoreRadioPlugin,
-            CoreRadioGroupPlugin,
-            CoreScrollbarPlugin,
-            CoreSliderPlugin,
-            CursorIconPlugin,
+            (
+                CoreBarrierPlugin,
+                CoreButtonPlugin,
+                CoreCheckboxPlugin,
+                CoreDialogPlugin,
+                CorePopoverPlugin,
+                CoreRadioPlugin,
+                CoreRadioGroupPlugin,
+                CoreScrollbarPlugin,
+                CoreScrubFieldPlugin,
+                CoreSliderPlugin,
+                CoreSpinBoxPlugin,
+                CoreTextAreaPlugin,
+                CoreTextInputPlugin,
+                CoreTooltipPlugin,
+                CursorIconPlugin,
+            ),
+            WidgetBindingPlugin,
         ))
+        .init_resource::<CallbackRegistry>()
+        .register_type::<hover::Hovering>()
+        .register_type::<ButtonPressed>()
+        .register_type::<Checked>()
+        .register_type::<InteractionReadOnly>()
+        .register_type::<Uncontrolled>()
+        .register_type::<InspectorField>()
+        .register_type::<InspectorSection>()
+        .register_type::<NamedCallbacks>()
         .add_systems(Update, hover::update_hover_states);
     }
 }
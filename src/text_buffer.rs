@@ -0,0 +1,392 @@
+use alloc::borrow::Cow;
+use core::ops::Range;
+
+use bevy::prelude::*;
+
+/// The editable contents of a text widget: the text itself, plus the cursor position and
+/// selection. All positions are character indices (not byte offsets) into the text.
+///
+/// The selection extends from the `anchor` to the `cursor`; when the two are equal, there is no
+/// selection.
+///
+/// While an input method (IME) is composing text, the uncommitted composition (the "preedit"
+/// text) is also stored here. It is not part of [`TextBuffer::text`], but is included in
+/// [`TextBuffer::display_text`] at the cursor position.
+#[derive(Component, Debug, Default, Clone, Reflect)]
+#[reflect(Component, Debug, Default)]
+pub struct TextBuffer {
+    text: String,
+    cursor: usize,
+    anchor: usize,
+    // The composition is transient input state, so it isn't saved.
+    #[reflect(ignore)]
+    preedit: String,
+    #[reflect(ignore)]
+    preedit_cursor: Option<Range<usize>>,
+    /// True if the text has been edited since the last commit.
+    pub(crate) uncommitted: bool,
+}
+
+/// Character classes used to find word boundaries.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CharClass {
+    Space,
+    Word,
+    Punctuation,
+}
+
+impl CharClass {
+    fn of(ch: char) -> Self {
+        if ch.is_whitespace() {
+            CharClass::Space
+        } else if ch.is_alphanumeric() || ch == '_' {
+            CharClass::Word
+        } else {
+            CharClass::Punctuation
+        }
+    }
+}
+
+impl TextBuffer {
+    /// Create a new buffer with the given text, and the cursor placed at the end.
+    pub fn new(text: impl Into<String>) -> Self {
+        let text = text.into();
+        let end = text.chars().count();
+        Self {
+            text,
+            cursor: end,
+            anchor: end,
+            preedit: String::new(),
+            preedit_cursor: None,
+            uncommitted: false,
+        }
+    }
+
+    /// The current text.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Replace the text, placing the cursor at the end and clearing the selection.
+    pub fn set_text(&mut self, text: impl Into<String>) {
+        *self = Self::new(text);
+    }
+
+    /// Length of the text, in characters.
+    pub fn len_chars(&self) -> usize {
+        self.text.chars().count()
+    }
+
+    /// Returns true if the buffer contains no text.
+    pub fn is_empty(&self) -> bool {
+        self.text.is_empty()
+    }
+
+    /// The character index of the cursor.
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    /// The character index of the selection anchor, that is, the end of the selection which
+    /// stays put when the selection is extended.
+    pub fn anchor(&self) -> usize {
+        self.anchor
+    }
+
+    /// The selected range of characters, in ascending order. This is empty if there is no
+    /// selection.
+    pub fn selection(&self) -> Range<usize> {
+        self.cursor.min(self.anchor)..self.cursor.max(self.anchor)
+    }
+
+    /// Returns true if any text is selected.
+    pub fn has_selection(&self) -> bool {
+        self.cursor != self.anchor
+    }
+
+    /// The currently selected text.
+    pub fn selected_text(&self) -> &str {
+        &self.text[self.byte_range(self.selection())]
+    }
+
+    /// Convert a character index into a byte offset into the text.
+    pub fn byte_offset(&self, pos: usize) -> usize {
+        self.text
+            .char_indices()
+            .nth(pos)
+            .map(|(offset, _)| offset)
+            .unwrap_or(self.text.len())
+    }
+
+    /// Convert a byte offset into the text into a character index.
+    pub fn char_index(&self, offset: usize) -> usize {
+        self.text[..offset.min(self.text.len())].chars().count()
+    }
+
+    /// Convert a range of character indices into a range of byte offsets.
+    pub fn byte_range(&self, range: Range<usize>) -> Range<usize> {
+        self.byte_offset(range.start)..self.byte_offset(range.end)
+    }
+
+    /// Move the cursor to `pos`. If `extend` is true, the selection anchor is left where it is,
+    /// otherwise the selection is cleared.
+    pub fn set_cursor(&mut self, pos: usize, extend: bool) {
+        self.cursor = pos.min(self.len_chars());
+        if !extend {
+            self.anchor = self.cursor;
+        }
+    }
+
+    /// Select the given range of characters, with the cursor at the end of the range.
+    pub fn select_range(&mut self, range: Range<usize>) {
+        let len = self.len_chars();
+        self.anchor = range.start.min(len);
+        self.cursor = range.end.min(len);
+    }
+
+    /// Select all of the text.
+    pub fn select_all(&mut self) {
+        self.select_range(0..self.len_chars());
+    }
+
+    /// Insert `text` at the cursor, replacing the selection if there is one.
+    pub fn insert(&mut self, text: &str) {
+        let range = self.byte_range(self.selection());
+        let start = self.selection().start;
+        self.text.replace_range(range, text);
+        self.set_cursor(start + text.chars().count(), false);
+        self.uncommitted = true;
+    }
+
+    /// Delete the selected text. Returns false if there was no selection.
+    pub fn delete_selection(&mut self) -> bool {
+        if !self.has_selection() {
+            return false;
+        }
+        self.insert("");
+        true
+    }
+
+    /// Delete the character (or word, if `word` is true) before the cursor, or the selection
+    /// if there is one. Returns true if anything was deleted.
+    pub fn delete_backward(&mut self, word: bool) -> bool {
+        if self.delete_selection() {
+            return true;
+        }
+        let start = if word {
+            self.prev_word_boundary(self.cursor)
+        } else {
+            self.cursor.saturating_sub(1)
+        };
+        if start == self.cursor {
+            return false;
+        }
+        self.anchor = start;
+        self.delete_selection()
+    }
+
+    /// Delete the character (or word, if `word` is true) after the cursor, or the selection
+    /// if there is one. Returns true if anything was deleted.
+    pub fn delete_forward(&mut self, word: bool) -> bool {
+        if self.delete_selection() {
+            return true;
+        }
+        let end = if word {
+            self.next_word_boundary(self.cursor)
+        } else {
+            (self.cursor + 1).min(self.len_chars())
+        };
+        if end == self.cursor {
+            return false;
+        }
+        self.anchor = end;
+        self.delete_selection()
+    }
+
+    /// Move the cursor one character (or word) to the left. If there is a selection and
+    /// `extend` is false, the cursor moves to the start of the selection instead.
+    pub fn move_left(&mut self, extend: bool, word: bool) {
+        if self.has_selection() && !extend && !word {
+            self.set_cursor(self.selection().start, false);
+        } else if word {
+            self.set_cursor(self.prev_word_boundary(self.cursor), extend);
+        } else {
+            self.set_cursor(self.cursor.saturating_sub(1), extend);
+        }
+    }
+
+    /// Move the cursor one character (or word) to the right. If there is a selection and
+    /// `extend` is false, the cursor moves to the end of the selection instead.
+    pub fn move_right(&mut self, extend: bool, word: bool) {
+        if self.has_selection() && !extend && !word {
+            self.set_cursor(self.selection().end, false);
+        } else if word {
+            self.set_cursor(self.next_word_boundary(self.cursor), extend);
+        } else {
+            self.set_cursor(self.cursor + 1, extend);
+        }
+    }
+
+    /// Move the cursor to the start of the text.
+    pub fn move_home(&mut self, extend: bool) {
+        self.set_cursor(0, extend);
+    }
+
+    /// Move the cursor to the end of the text.
+    pub fn move_end(&mut self, extend: bool) {
+        self.set_cursor(self.len_chars(), extend);
+    }
+
+    /// Find the start of the word before `pos`, skipping any whitespace in between.
+    pub fn prev_word_boundary(&self, pos: usize) -> usize {
+        let chars: Vec<char> = self.text.chars().take(pos).collect();
+        let mut index = chars.len();
+        while index > 0 && CharClass::of(chars[index - 1]) == CharClass::Space {
+            index -= 1;
+        }
+        if index > 0 {
+            let class = CharClass::of(chars[index - 1]);
+            while index > 0 && CharClass::of(chars[index - 1]) == class {
+                index -= 1;
+            }
+        }
+        index
+    }
+
+    /// Find the end of the word after `pos`, skipping any whitespace that follows it.
+    pub fn next_word_boundary(&self, pos: usize) -> usize {
+        let chars: Vec<char> = self.text.chars().collect();
+        let mut index = pos.min(chars.len());
+        if index < chars.len() {
+            let class = CharClass::of(chars[index]);
+            while index < chars.len() && CharClass::of(chars[index]) == class {
+                index += 1;
+            }
+        }
+        while index < chars.len() && CharClass::of(chars[index]) == CharClass::Space {
+            index += 1;
+        }
+        index
+    }
+
+    /// The range of the word (or run of whitespace or punctuation) containing `pos`.
+    pub fn word_at(&self, pos: usize) -> Range<usize> {
+        let chars: Vec<char> = self.text.chars().collect();
+        if chars.is_empty() {
+            return 0..0;
+        }
+        let pos = pos.min(chars.len() - 1);
+        let class = CharClass::of(chars[pos]);
+        let mut start = pos;
+        while start > 0 && CharClass::of(chars[start - 1]) == class {
+            start -= 1;
+        }
+        let mut end = pos;
+        while end < chars.len() && CharClass::of(chars[end]) == class {
+            end += 1;
+        }
+        start..end
+    }
+
+    /// The range of the line (that is, the paragraph, delimited by newlines) containing `pos`.
+    /// The range does not include the newline character.
+    pub fn line_at(&self, pos: usize) -> Range<usize> {
+        let mut start = 0;
+        for (index, ch) in self.text.chars().enumerate() {
+            if ch == '\n' {
+                if index >= pos {
+                    return start..index;
+                }
+                start = index + 1;
+            }
+        }
+        start..self.len_chars()
+    }
+
+    /// The text being composed by the input method, if any.
+    pub fn preedit(&self) -> &str {
+        &self.preedit
+    }
+
+    /// Returns true if the input method is currently composing text.
+    pub fn is_composing(&self) -> bool {
+        !self.preedit.is_empty()
+    }
+
+    /// Set the text being composed by the input method. `cursor` is the input method's cursor
+    /// (or selection) within the preedit text, as byte offsets; `None` means the cursor should
+    /// be hidden.
+    pub fn set_preedit(&mut self, preedit: impl Into<String>, cursor: Option<(usize, usize)>) {
+        self.preedit = preedit.into();
+        self.preedit_cursor = cursor.map(|(start, end)| {
+            let to_char = |offset: usize| {
+                self.preedit[..offset.min(self.preedit.len())]
+                    .chars()
+                    .count()
+            };
+            to_char(start)..to_char(end)
+        });
+    }
+
+    /// Discard the text being composed by the input method.
+    pub fn clear_preedit(&mut self) {
+        self.preedit.clear();
+        self.preedit_cursor = None;
+    }
+
+    /// The text as it should be displayed: the buffer text with any preedit text inserted at
+    /// the cursor.
+    pub fn display_text(&self) -> Cow<'_, str> {
+        if self.preedit.is_empty() {
+            Cow::Borrowed(&self.text)
+        } else {
+            let offset = self.byte_offset(self.cursor);
+            let mut text = String::with_capacity(self.text.len() + self.preedit.len());
+            text.push_str(&self.text[..offset]);
+            text.push_str(&self.preedit);
+            text.push_str(&self.text[offset..]);
+            Cow::Owned(text)
+        }
+    }
+
+    /// The range of characters in [`TextBuffer::display_text`] occupied by the preedit text.
+    /// Styling code can use this to underline the composition. Empty if not composing.
+    pub fn preedit_range(&self) -> Range<usize> {
+        self.cursor..self.cursor + self.preedit.chars().count()
+    }
+
+    /// Convert a character index in [`TextBuffer::display_text`] into the corresponding index in
+    /// the buffer's text. Indices within the preedit text map to the cursor, where it will be
+    /// inserted.
+    pub fn buffer_index(&self, display_index: usize) -> usize {
+        let preedit = self.preedit_range();
+        if display_index <= preedit.start {
+            display_index
+        } else if display_index < preedit.end {
+            preedit.start
+        } else {
+            display_index - preedit.len()
+        }
+    }
+
+    /// The position of the caret in [`TextBuffer::display_text`]. While composing, this is the
+    /// input method's cursor within the preedit text; otherwise it is the buffer's cursor.
+    pub fn display_cursor(&self) -> usize {
+        match &self.preedit_cursor {
+            Some(cursor) if self.is_composing() => self.cursor + cursor.end,
+            _ => self.cursor,
+        }
+    }
+
+    /// The range of characters in [`TextBuffer::display_text`] which should be drawn as
+    /// selected. While composing, this is the input method's selection within the preedit text.
+    pub fn display_selection(&self) -> Range<usize> {
+        match &self.preedit_cursor {
+            Some(cursor) if self.is_composing() => {
+                self.cursor + cursor.start..self.cursor + cursor.end
+            }
+            _ if self.is_composing() => self.cursor..self.cursor,
+            _ => self.selection(),
+        }
+    }
+}
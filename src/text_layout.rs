@@ -0,0 +1,249 @@
+use core::ops::Range;
+
+use bevy::{prelude::*, text::TextLayoutInfo};
+
+/// The top-left corner of the content box of a text node, in physical pixels. This is the origin
+/// of the coordinate space used by the node's [`TextLayoutInfo`].
+pub fn text_origin(node: &ComputedNode, transform: &GlobalTransform) -> Vec2 {
+    let inset = node.content_inset();
+    transform.translation().truncate() - node.size() * 0.5 + Vec2::new(inset.left, inset.top)
+}
+
+/// A glyph from a [`TextLayoutInfo`], mapped back to the range of characters it was shaped from.
+#[derive(Debug, Clone)]
+pub struct GlyphBox {
+    /// The range of character indices in the source text covered by this glyph.
+    pub chars: Range<usize>,
+    /// The bounding box of the glyph, in the coordinate space of the text layout.
+    pub rect: Rect,
+}
+
+/// A single visual line of laid-out text. A paragraph which has been soft-wrapped produces
+/// multiple visual lines.
+#[derive(Debug, Clone)]
+pub struct TextLine {
+    /// The range of character indices in the source text displayed on this line. This does not
+    /// include the newline character which ends the paragraph, if any.
+    pub chars: Range<usize>,
+    /// The glyphs on this line, in visual order.
+    pub glyphs: Range<usize>,
+    /// The top edge of the line, in the coordinate space of the text layout.
+    pub top: f32,
+    /// The bottom edge of the line, in the coordinate space of the text layout.
+    pub bottom: f32,
+}
+
+/// Geometry of a block of laid-out text, which maps between character indices in the source
+/// text and positions in the text layout. This is used to place the caret and selection
+/// highlight of text editing widgets.
+///
+/// Positions are in the coordinate space of the [`TextLayoutInfo`]: physical pixels, relative to
+/// the top-left corner of the text node's content box. Multiply by
+/// [`ComputedNode::inverse_scale_factor`] to convert to logical pixels.
+///
+/// This assumes left-to-right text, and that every line has the same height.
+#[derive(Debug, Clone, Default)]
+pub struct TextGeometry {
+    glyphs: Vec<GlyphBox>,
+    lines: Vec<TextLine>,
+}
+
+impl TextGeometry {
+    /// Build the geometry for `text`, which must be the text that `layout` was computed from.
+    pub fn new(text: &str, layout: &TextLayoutInfo) -> Self {
+        // Byte offset of the start of each character, plus a sentinel for the end of the text.
+        let char_starts: Vec<usize> = text
+            .char_indices()
+            .map(|(offset, _)| offset)
+            .chain(core::iter::once(text.len()))
+            .collect();
+        let to_char = |byte: usize| char_starts.partition_point(|start| *start < byte);
+
+        // Character range of each paragraph, excluding the trailing newline.
+        let mut paragraphs: Vec<Range<usize>> = Vec::new();
+        let mut para_bytes: Vec<usize> = Vec::new();
+        let mut start = 0;
+        for (offset, ch) in text.char_indices() {
+            if ch == '\n' {
+                para_bytes.push(start);
+                paragraphs.push(to_char(start)..to_char(offset));
+                start = offset + 1;
+            }
+        }
+        para_bytes.push(start);
+        paragraphs.push(to_char(start)..to_char(text.len()));
+
+        let mut geometry = Self::default();
+        let mut glyphs = layout.glyphs.iter().peekable();
+        for (index, paragraph) in paragraphs.iter().enumerate() {
+            let mut row_start = geometry.glyphs.len();
+            let mut prev_x = f32::NEG_INFINITY;
+            while let Some(glyph) = glyphs.next_if(|glyph| glyph.line_index <= index) {
+                let byte = para_bytes[index.min(glyph.line_index)] + glyph.byte_index;
+                let rect = Rect::from_center_size(glyph.position, glyph.size);
+                // A glyph to the left of the previous one means the paragraph has wrapped.
+                if rect.min.x < prev_x && geometry.glyphs.len() > row_start {
+                    geometry.push_line(row_start, None);
+                    row_start = geometry.glyphs.len();
+                }
+                prev_x = rect.min.x;
+                geometry.glyphs.push(GlyphBox {
+                    chars: to_char(byte)..to_char(byte + glyph.byte_length),
+                    rect,
+                });
+            }
+            geometry.push_line(row_start, Some(paragraph.clone()));
+        }
+
+        // Assign vertical extents, assuming uniform line height.
+        let line_height = layout.size.y / geometry.lines.len() as f32;
+        for (index, line) in geometry.lines.iter_mut().enumerate() {
+            line.top = index as f32 * line_height;
+            line.bottom = line.top + line_height;
+        }
+        geometry
+    }
+
+    /// Add a visual line containing the glyphs from `row_start` onwards. If `paragraph` is
+    /// given, this is the last line of that paragraph.
+    fn push_line(&mut self, row_start: usize, paragraph: Option<Range<usize>>) {
+        let glyphs = row_start..self.glyphs.len();
+        let start = match (self.glyphs.get(row_start), &paragraph) {
+            (Some(glyph), Some(paragraph)) => glyph.chars.start.min(paragraph.end),
+            (Some(glyph), None) => glyph.chars.start,
+            (None, Some(paragraph)) => paragraph.start,
+            (None, None) => 0,
+        };
+        // Wrapped lines end where the next one starts; the last line ends with the paragraph.
+        if let Some(prev) = self.lines.last_mut() {
+            if prev.chars.end == usize::MAX {
+                prev.chars.end = start;
+            }
+        }
+        let end = match paragraph {
+            Some(paragraph) => paragraph.end,
+            None => usize::MAX,
+        };
+        self.lines.push(TextLine {
+            chars: start..end,
+            glyphs,
+            top: 0.,
+            bottom: 0.,
+        });
+    }
+
+    /// The glyphs of the text, in visual order.
+    pub fn glyphs(&self) -> &[GlyphBox] {
+        &self.glyphs
+    }
+
+    /// The visual lines of the text, from top to bottom.
+    pub fn lines(&self) -> &[TextLine] {
+        &self.lines
+    }
+
+    /// The index of the visual line containing the character index `pos`. A position at a soft
+    /// wrap is considered to be at the start of the following line.
+    pub fn line_at(&self, pos: usize) -> usize {
+        self.lines
+            .iter()
+            .rposition(|line| line.chars.start <= pos)
+            .unwrap_or(0)
+    }
+
+    /// The horizontal position of a caret placed before the character `pos`, on the given line.
+    pub fn caret_x(&self, line: usize, pos: usize) -> f32 {
+        let Some(line) = self.lines.get(line) else {
+            return 0.;
+        };
+        let glyphs = &self.glyphs[line.glyphs.clone()];
+        if let Some(glyph) = glyphs.iter().find(|glyph| glyph.chars.start >= pos) {
+            return glyph.rect.min.x;
+        }
+        match glyphs.last() {
+            Some(last) if last.rect.width() > 0. => last.rect.max.x,
+            // Blank glyphs such as spaces have no width; estimate the advance from the others.
+            Some(last) => last.rect.min.x + self.average_advance(glyphs),
+            None => 0.,
+        }
+    }
+
+    /// Average distance between the start of consecutive glyphs on a line.
+    fn average_advance(&self, glyphs: &[GlyphBox]) -> f32 {
+        match (glyphs.first(), glyphs.len()) {
+            (Some(first), count) if count > 1 => {
+                (glyphs[count - 1].rect.min.x - first.rect.min.x) / (count - 1) as f32
+            }
+            _ => 0.,
+        }
+    }
+
+    /// The rectangle occupied by a caret placed before the character `pos`. The rectangle has
+    /// zero width and spans the height of the line.
+    pub fn caret_rect(&self, pos: usize) -> Rect {
+        let line_index = self.line_at(pos);
+        let x = self.caret_x(line_index, pos);
+        match self.lines.get(line_index) {
+            Some(line) => Rect::new(x, line.top, x, line.bottom),
+            None => Rect::new(x, 0., x, 0.),
+        }
+    }
+
+    /// The character index on the given line which is closest to the horizontal position `x`.
+    /// This is the inverse of [`TextGeometry::caret_x`].
+    pub fn char_at_x(&self, line: usize, x: f32) -> usize {
+        let Some(text_line) = self.lines.get(line) else {
+            return self.lines.last().map(|line| line.chars.end).unwrap_or(0);
+        };
+        let glyphs = &self.glyphs[text_line.glyphs.clone()];
+        for (index, glyph) in glyphs.iter().enumerate() {
+            let end = glyphs
+                .get(index + 1)
+                .map(|next| next.rect.min.x)
+                .unwrap_or(glyph.rect.max.x);
+            if x < (glyph.rect.min.x + end) * 0.5 {
+                return glyph.chars.start;
+            }
+        }
+        // If the line was soft-wrapped, its end is the start of the next line; stay on this
+        // line by placing the cursor before the character at the wrap point instead.
+        let wrapped = self
+            .lines
+            .get(line + 1)
+            .is_some_and(|next| next.chars.start == text_line.chars.end);
+        if wrapped {
+            text_line
+                .chars
+                .end
+                .saturating_sub(1)
+                .max(text_line.chars.start)
+        } else {
+            text_line.chars.end
+        }
+    }
+
+    /// The character index closest to `point`, which is in the coordinate space of the text
+    /// layout. Points above or below the text map to the first or last line.
+    pub fn hit_test(&self, point: Vec2) -> usize {
+        let line = self
+            .lines
+            .iter()
+            .position(|line| point.y < line.bottom)
+            .unwrap_or(self.lines.len().saturating_sub(1));
+        self.char_at_x(line, point.x)
+    }
+}
+
+/// Convert a pointer position, in logical window coordinates, into the character index in `text`
+/// which is closest to it. `text` must be the text that `layout` was computed from, and `node`
+/// and `transform` must belong to the same text entity.
+pub fn hit_test_text(
+    text: &str,
+    layout: &TextLayoutInfo,
+    node: &ComputedNode,
+    transform: &GlobalTransform,
+    position: Vec2,
+) -> usize {
+    let point = position / node.inverse_scale_factor - text_origin(node, transform);
+    TextGeometry::new(text, layout).hit_test(point)
+}
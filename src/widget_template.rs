@@ -0,0 +1,525 @@
+use alloc::sync::Arc;
+use core::fmt;
+use std::io;
+
+use bevy::{
+    asset::{io::Reader, ron, AssetLoader, LoadContext},
+    platform::collections::{HashMap, HashSet},
+    prelude::*,
+    reflect::{serde::TypedReflectDeserializer, TypeRegistry, TypeRegistryArc},
+};
+use serde::de::{self, Deserialize, DeserializeSeed, Deserializer, MapAccess, SeqAccess, Visitor};
+
+use crate::{CallbackRegistry, NamedCallbacks};
+
+/// An asset which describes a tree of widgets, loaded from a `.widget.ron` file. Each node of the
+/// tree is a struct with the following optional fields:
+///
+/// * `components`: a map from a component type to its value, in the same format as in a scene
+///   file. The type can be given by its full type path, or by its short name if that is
+///   unambiguous. Components must be registered with [`ReflectComponent`] type data; all of the
+///   core widgets are. Fields which are left out take their default values.
+/// * `callbacks`: a map from a callback field, such as `on_click`, to the name of a callback in
+///   the [`CallbackRegistry`]. See [`NamedCallbacks`].
+/// * `children`: a list of child nodes.
+/// * `key`: a name which identifies the node when the file is reloaded. By default, nodes are
+///   identified by their position in the tree, so giving a node a key preserves its entity when
+///   it is moved, even to a different parent. Keys must be unique within the file.
+///
+/// ```ron
+/// (
+///     components: {
+///         "Node": (flex_direction: Column, row_gap: Px(8.0)),
+///     },
+///     children: [
+///         (
+///             key: "volume",
+///             components: {
+///                 "Node": (width: Px(200.0), height: Px(12.0)),
+///                 "CoreSlider": (value: 50.0, max: 100.0),
+///             },
+///             callbacks: { "on_change": "set_volume" },
+///         ),
+///         (
+///             components: { "CoreButton": (), "Node": () },
+///             callbacks: { "on_click": "save" },
+///             children: [(components: { "Text": ("Save") })],
+///         ),
+///     ],
+/// )
+/// ```
+///
+/// The tree is spawned by adding a [`WidgetTemplateRoot`] component to an entity.
+#[derive(Asset, TypePath, Debug)]
+pub struct WidgetTemplate {
+    root: Arc<TemplateNode>,
+}
+
+/// A node of a [`WidgetTemplate`].
+#[derive(Debug, Default)]
+struct TemplateNode {
+    key: Option<String>,
+    /// Component values, keyed by the full type path.
+    components: Vec<(String, Box<dyn PartialReflect>)>,
+    callbacks: HashMap<String, String>,
+    children: Vec<TemplateNode>,
+}
+
+impl TemplateNode {
+    fn component(&self, type_path: &str) -> Option<&dyn PartialReflect> {
+        self.components
+            .iter()
+            .find(|(path, _)| path == type_path)
+            .map(|(_, value)| value.as_ref())
+    }
+
+    /// Get the key of each child, which is either its explicit key, or its position.
+    fn child_keys(&self, key: &str) -> Vec<(String, &TemplateNode)> {
+        self.children
+            .iter()
+            .enumerate()
+            .map(|(index, child)| {
+                let child_key = child
+                    .key
+                    .clone()
+                    .unwrap_or_else(|| format!("{key}/{index}"));
+                (child_key, child)
+            })
+            .collect()
+    }
+
+    /// Find a key which is used by more than one node of this subtree, or by a node in `keys`.
+    fn duplicate_key(&self, key: String, keys: &mut HashSet<String>) -> Option<String> {
+        if !keys.insert(key.clone()) {
+            return Some(key);
+        }
+        self.child_keys(&key)
+            .into_iter()
+            .find_map(|(child_key, child)| child.duplicate_key(child_key, keys))
+    }
+
+    /// Add this node and all of its descendants to a map keyed by node key.
+    fn collect<'a>(&'a self, key: String, nodes: &mut HashMap<String, &'a TemplateNode>) {
+        for (child_key, child) in self.child_keys(&key) {
+            child.collect(child_key, nodes);
+        }
+        nodes.insert(key, self);
+    }
+}
+
+/// Component which spawns the widgets described by a [`WidgetTemplate`] as descendants of this
+/// entity. The components of the root node of the template are inserted on this entity.
+///
+/// The widgets are spawned once the template has loaded, and are updated if the template
+/// changes - for example when the file is edited, if the [`AssetPlugin`] is watching for
+/// changes. When the template is reloaded, entities are matched to template nodes by their key,
+/// and the entities of nodes which still exist are kept. Only the components which have changed
+/// in the file are replaced, so the state of a widget, such as the value of a slider, is kept
+/// unless its description has been edited.
+#[derive(Component, Debug, Clone, Default, Reflect)]
+#[reflect(Component, Debug, Default)]
+pub struct WidgetTemplateRoot(pub Handle<WidgetTemplate>);
+
+/// Tracks which template was spawned on a [`WidgetTemplateRoot`], and the entities of its
+/// nodes, so that they can be updated when the template changes.
+#[derive(Component)]
+struct WidgetTemplateInstance {
+    template: AssetId<WidgetTemplate>,
+    root: Arc<TemplateNode>,
+    entities: HashMap<String, Entity>,
+}
+
+fn update_widget_templates(
+    mut events: EventReader<AssetEvent<WidgetTemplate>>,
+    q_roots: Query<(Entity, &WidgetTemplateRoot, Option<&WidgetTemplateInstance>)>,
+    templates: Res<Assets<WidgetTemplate>>,
+    mut commands: Commands,
+) {
+    let modified: HashSet<AssetId<WidgetTemplate>> = events
+        .read()
+        .filter_map(|event| match event {
+            AssetEvent::Modified { id } => Some(*id),
+            _ => None,
+        })
+        .collect();
+    for (entity, root, instance) in q_roots.iter() {
+        let id = root.0.id();
+        let stale = match instance {
+            Some(instance) => instance.template != id || modified.contains(&id),
+            None => true,
+        };
+        if stale && templates.contains(id) {
+            commands.queue(move |world: &mut World| spawn_widget_template(world, entity));
+        }
+    }
+}
+
+/// Spawn or update the widgets of the template on a [`WidgetTemplateRoot`].
+fn spawn_widget_template(world: &mut World, root: Entity) {
+    let Some(id) = world.get::<WidgetTemplateRoot>(root).map(|r| r.0.id()) else {
+        return;
+    };
+    let Some(template) = world
+        .resource::<Assets<WidgetTemplate>>()
+        .get(id)
+        .map(|template| template.root.clone())
+    else {
+        return;
+    };
+    let registry = world.resource::<AppTypeRegistry>().clone();
+    let registry = registry.read();
+
+    let previous = world.entity_mut(root).take::<WidgetTemplateInstance>();
+    let mut old_nodes = HashMap::new();
+    let mut old_entities = HashMap::new();
+    if let Some(previous) = &previous {
+        previous.root.collect(String::new(), &mut old_nodes);
+        old_entities = previous.entities.clone();
+        old_entities.remove("");
+    }
+
+    let mut entities = HashMap::new();
+    apply_node(
+        world,
+        &registry,
+        &mut NodeEntities {
+            old_nodes: &old_nodes,
+            old_entities: &mut old_entities,
+            entities: &mut entities,
+        },
+        root,
+        &template,
+        String::new(),
+        previous.as_ref().map(|previous| previous.root.as_ref()),
+    );
+
+    // Despawn the entities of nodes which have been removed from the template.
+    for entity in old_entities.into_values() {
+        if let Ok(entity) = world.get_entity_mut(entity) {
+            entity.despawn();
+        }
+    }
+
+    world.entity_mut(root).insert(WidgetTemplateInstance {
+        template: id,
+        root: template,
+        entities,
+    });
+}
+
+/// The entities of the previous and current versions of a template instance.
+struct NodeEntities<'a> {
+    old_nodes: &'a HashMap<String, &'a TemplateNode>,
+    old_entities: &'a mut HashMap<String, Entity>,
+    entities: &'a mut HashMap<String, Entity>,
+}
+
+/// Update an entity to match a template node, and then its children. `old` is the node the
+/// entity was previously spawned from, if any.
+fn apply_node(
+    world: &mut World,
+    registry: &TypeRegistry,
+    state: &mut NodeEntities,
+    entity: Entity,
+    node: &TemplateNode,
+    key: String,
+    old: Option<&TemplateNode>,
+) {
+    let mut entity_mut = world.entity_mut(entity);
+    if let Some(old) = old {
+        // Remove components which are no longer in the template.
+        for (type_path, _) in &old.components {
+            if node.component(type_path).is_none() {
+                if let Some(reflect_component) = registry
+                    .get_with_type_path(type_path)
+                    .and_then(|registration| registration.data::<ReflectComponent>())
+                {
+                    reflect_component.remove(&mut entity_mut);
+                }
+            }
+        }
+    }
+    for (type_path, value) in &node.components {
+        // Keep the current value of components which haven't changed.
+        let unchanged = old
+            .and_then(|old| old.component(type_path))
+            .and_then(|old_value| old_value.reflect_partial_eq(value.as_ref()))
+            .unwrap_or(false);
+        if unchanged {
+            continue;
+        }
+        if let Some(reflect_component) = registry
+            .get_with_type_path(type_path)
+            .and_then(|registration| registration.data::<ReflectComponent>())
+        {
+            reflect_component.insert(&mut entity_mut, value.as_ref(), registry);
+        }
+    }
+    // Re-insert the callback names, since the widget component may have been replaced.
+    if !node.callbacks.is_empty() {
+        entity_mut.insert(NamedCallbacks(node.callbacks.clone()));
+    } else if old.is_some_and(|old| !old.callbacks.is_empty()) {
+        entity_mut.remove::<NamedCallbacks>();
+    }
+
+    for (child_key, child) in node.child_keys(&key) {
+        let reused = state
+            .old_entities
+            .remove(&child_key)
+            .filter(|child| world.get_entity(*child).is_ok());
+        let child_entity = reused.unwrap_or_else(|| world.spawn_empty().id());
+        // Inserting `ChildOf` moves the child to the end, which keeps the children in order.
+        world.entity_mut(child_entity).insert(ChildOf(entity));
+        let old_child = reused.and_then(|_| state.old_nodes.get(&child_key).copied());
+        apply_node(
+            world,
+            registry,
+            state,
+            child_entity,
+            child,
+            child_key,
+            old_child,
+        );
+    }
+    state.entities.insert(key, entity);
+}
+
+/// Asset loader for [`WidgetTemplate`] files (`.widget.ron`).
+#[derive(Debug)]
+pub struct WidgetTemplateLoader {
+    type_registry: TypeRegistryArc,
+}
+
+impl FromWorld for WidgetTemplateLoader {
+    fn from_world(world: &mut World) -> Self {
+        Self {
+            type_registry: world.resource::<AppTypeRegistry>().0.clone(),
+        }
+    }
+}
+
+/// Errors which can occur when loading a [`WidgetTemplate`].
+#[derive(Debug)]
+pub enum WidgetTemplateLoaderError {
+    /// The file couldn't be read.
+    Io(io::Error),
+    /// The file isn't a valid template.
+    Ron(ron::error::SpannedError),
+    /// More than one node has the given key.
+    DuplicateKey(String),
+}
+
+impl fmt::Display for WidgetTemplateLoaderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "Error while reading the widget template: {error}"),
+            Self::Ron(error) => write!(f, "Could not parse the widget template: {error}"),
+            Self::DuplicateKey(key) => {
+                write!(
+                    f,
+                    "The widget template has more than one node with key `{key}`"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for WidgetTemplateLoaderError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(error) => Some(error),
+            Self::Ron(error) => Some(error),
+            Self::DuplicateKey(_) => None,
+        }
+    }
+}
+
+impl From<io::Error> for WidgetTemplateLoaderError {
+    fn from(error: io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+impl From<ron::error::SpannedError> for WidgetTemplateLoaderError {
+    fn from(error: ron::error::SpannedError) -> Self {
+        Self::Ron(error)
+    }
+}
+
+impl AssetLoader for WidgetTemplateLoader {
+    type Asset = WidgetTemplate;
+    type Settings = ();
+    type Error = WidgetTemplateLoaderError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let mut deserializer = ron::de::Deserializer::from_bytes(&bytes)?;
+        let root = TemplateNodeSeed {
+            registry: &self.type_registry.read(),
+        }
+        .deserialize(&mut deserializer)
+        .map_err(|error| deserializer.span_error(error))?;
+        // Nodes are tracked by key across reloads, so each key must identify a single node.
+        if let Some(key) = root.duplicate_key(String::new(), &mut HashSet::new()) {
+            return Err(WidgetTemplateLoaderError::DuplicateKey(key));
+        }
+        Ok(WidgetTemplate {
+            root: Arc::new(root),
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["widget.ron"]
+    }
+}
+
+const NODE_FIELDS: &[&str] = &["key", "components", "callbacks", "children"];
+
+/// Deserializer for a [`TemplateNode`], which uses the type registry to deserialize components.
+#[derive(Clone, Copy)]
+struct TemplateNodeSeed<'a> {
+    registry: &'a TypeRegistry,
+}
+
+impl<'de> DeserializeSeed<'de> for TemplateNodeSeed<'_> {
+    type Value = TemplateNode;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_struct("WidgetNode", NODE_FIELDS, self)
+    }
+}
+
+impl<'de> Visitor<'de> for TemplateNodeSeed<'_> {
+    type Value = TemplateNode;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a widget node")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut node = TemplateNode::default();
+        while let Some(Ident(field)) = map.next_key()? {
+            match field.as_str() {
+                "key" => node.key = Some(map.next_value()?),
+                "components" => node.components = map.next_value_seed(ComponentsSeed(self))?,
+                "callbacks" => node.callbacks = map.next_value()?,
+                "children" => node.children = map.next_value_seed(ChildrenSeed(self))?,
+                _ => return Err(de::Error::unknown_field(&field, NODE_FIELDS)),
+            }
+        }
+        Ok(node)
+    }
+}
+
+/// A struct field name.
+struct Ident(String);
+
+impl<'de> Deserialize<'de> for Ident {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct IdentVisitor;
+
+        impl Visitor<'_> for IdentVisitor {
+            type Value = Ident;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("an identifier")
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+                Ok(Ident(value.to_string()))
+            }
+        }
+
+        deserializer.deserialize_identifier(IdentVisitor)
+    }
+}
+
+/// Deserializer for the map of components of a node.
+struct ComponentsSeed<'a>(TemplateNodeSeed<'a>);
+
+impl<'de> DeserializeSeed<'de> for ComponentsSeed<'_> {
+    type Value = Vec<(String, Box<dyn PartialReflect>)>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de> Visitor<'de> for ComponentsSeed<'_> {
+    type Value = Vec<(String, Box<dyn PartialReflect>)>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a map of components")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let registry = self.0.registry;
+        let mut components = Vec::new();
+        while let Some(name) = map.next_key::<String>()? {
+            let registration = registry
+                .get_with_type_path(&name)
+                .or_else(|| registry.get_with_short_type_path(&name))
+                .ok_or_else(|| {
+                    de::Error::custom(format_args!("no registered type named `{name}`"))
+                })?;
+            if registration.data::<ReflectComponent>().is_none() {
+                return Err(de::Error::custom(format_args!(
+                    "`{name}` is not registered as a component"
+                )));
+            }
+            let value =
+                map.next_value_seed(TypedReflectDeserializer::new(registration, registry))?;
+            components.push((registration.type_info().type_path().to_string(), value));
+        }
+        Ok(components)
+    }
+}
+
+/// Deserializer for the list of children of a node.
+struct ChildrenSeed<'a>(TemplateNodeSeed<'a>);
+
+impl<'de> DeserializeSeed<'de> for ChildrenSeed<'_> {
+    type Value = Vec<TemplateNode>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'de> Visitor<'de> for ChildrenSeed<'_> {
+    type Value = Vec<TemplateNode>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a list of widget nodes")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut children = Vec::new();
+        while let Some(child) = seq.next_element_seed(self.0)? {
+            children.push(child);
+        }
+        Ok(children)
+    }
+}
+
+/// Plugin which loads [`WidgetTemplate`] assets and spawns them on [`WidgetTemplateRoot`]
+/// entities. This requires the [`AssetPlugin`], so unlike the widget plugins it is not part of
+/// [`CoreWidgetsPlugin`](crate::CoreWidgetsPlugin); apps which use templates should add it.
+pub struct WidgetTemplatePlugin;
+
+impl Plugin for WidgetTemplatePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<WidgetTemplate>()
+            .init_asset_loader::<WidgetTemplateLoader>()
+            .init_resource::<CallbackRegistry>()
+            .add_systems(Update, update_widget_templates)
+            .register_type::<WidgetTemplateRoot>()
+            .register_type::<NamedCallbacks>();
+    }
+}